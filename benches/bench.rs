@@ -1,10 +1,11 @@
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::io::BufRead;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use criterion::{black_box, Bencher, Criterion};
 use criterion::{criterion_group, criterion_main};
-use radix_heap::RadixHeapMap;
+use radix_heap::{PackedKey, Radix, RadixHeapMap};
 
 type Pos = (u32, u32);
 
@@ -187,6 +188,73 @@ impl AStarHeap for RadixHeapMap<Reverse<u32>, (Pos, u32)> {
     }
 }
 
+impl AStarHeap for RadixHeapMap<(Reverse<u32>, Reverse<u32>), Pos> {
+    #[inline]
+    fn new() -> Self {
+        RadixHeapMap::new()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    #[inline]
+    fn push(&mut self, entry: AStarEntry) {
+        // Breaking ties by the greater cost like `AStarEntry` would not be
+        // monotone, as neighbors have the same full cost and a greater cost
+        self.push((Reverse(entry.full_cost), Reverse(entry.cost)), entry.pos)
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<AStarEntry> {
+        self.pop()
+            .map(|((Reverse(full_cost), Reverse(cost)), pos)| AStarEntry {
+                pos,
+                cost,
+                full_cost,
+            })
+    }
+}
+
+impl AStarHeap for RadixHeapMap<PackedKey, Pos> {
+    #[inline]
+    fn new() -> Self {
+        RadixHeapMap::new()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    #[inline]
+    fn push(&mut self, entry: AStarEntry) {
+        // Ordered like the tuple key above
+        let key = PackedKey::builder()
+            .reverse(entry.full_cost.into(), 32)
+            .reverse(entry.cost.into(), 32)
+            .build();
+
+        self.push(key, entry.pos)
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<AStarEntry> {
+        self.pop().map(|(key, pos)| {
+            let mut reader = key.reader();
+            let full_cost = reader.reverse(32) as u32;
+            let cost = reader.reverse(32) as u32;
+
+            AStarEntry {
+                pos,
+                cost,
+                full_cost,
+            }
+        })
+    }
+}
+
 impl AStarHeap for BinaryHeap<AStarEntry> {
     #[inline]
     fn new() -> Self {
@@ -245,14 +313,87 @@ fn pushpop_binary(b: &mut Bencher) {
     });
 }
 
+/// The number of radix similarities computed by `Counted` keys. The heap
+/// computes one for every push once a top key is set, and one for every item
+/// it redistributes.
+static SIMILARITIES: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Counted<T>(T);
+
+impl<T: Radix> Radix for Counted<T> {
+    #[inline]
+    fn radix_similarity(&self, other: &Self) -> u32 {
+        SIMILARITIES.fetch_add(1, Ordering::Relaxed);
+        self.0.radix_similarity(&other.0)
+    }
+
+    const RADIX_BITS: u32 = T::RADIX_BITS;
+}
+
+/// Like `pushpop_radix`, but starting at `start` and pushing keys strictly
+/// below each popped one, so that they keep moving down from it.
+/// Returns the number of pushes made with a top key set, which excludes the
+/// first one.
+fn pushpop_counted<T: Radix + Ord + Copy>(
+    heap: &mut RadixHeapMap<Counted<T>, ()>,
+    start: T,
+    sub: fn(T, u8) -> T,
+) -> usize {
+    heap.push(Counted(start), ());
+
+    for _ in 0..10000 {
+        let (Counted(n), _) = heap.pop().unwrap();
+
+        for i in 0..4 {
+            heap.push(Counted(sub(n, i + 1)), ());
+        }
+    }
+
+    heap.clear();
+    10000 * 4
+}
+
+/// Benchmarks `pushpop_counted`, after printing how many items it
+/// redistributes between buckets.
+fn bench_redistributions<T: Radix + Ord + Copy>(
+    c: &mut Criterion,
+    name: &str,
+    start: T,
+    sub: fn(T, u8) -> T,
+) {
+    let mut heap = RadixHeapMap::new();
+
+    SIMILARITIES.store(0, Ordering::Relaxed);
+    let pushes = pushpop_counted(&mut heap, start, sub);
+    let redistributions = SIMILARITIES.load(Ordering::Relaxed) - pushes;
+    println!("{}: {} redistributions", name, redistributions);
+
+    c.bench_function(name, |b| {
+        b.iter(|| pushpop_counted(&mut heap, start, sub));
+    });
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function(
         "astar_radix",
         astar::<RadixHeapMap<Reverse<u32>, (Pos, u32)>>,
     );
+    c.bench_function(
+        "astar_radix_tuple",
+        astar::<RadixHeapMap<(Reverse<u32>, Reverse<u32>), Pos>>,
+    );
+    c.bench_function("astar_radix_packed", astar::<RadixHeapMap<PackedKey, Pos>>);
     c.bench_function("astar_binary", astar::<BinaryHeap<AStarEntry>>);
     c.bench_function("pushpop_radix", pushpop_radix);
     c.bench_function("pushpop_binary", pushpop_binary);
+
+    // Signed keys crossing zero are bucketed like unsigned keys crossing the
+    // same power of two, so these redistribute the same number of items
+    bench_redistributions(c, "redistribute_signed", 2000i32, |n, i| n - i32::from(i));
+    bench_redistributions(c, "redistribute_unsigned", (1u32 << 31) + 2000, |n, i| {
+        n - u32::from(i)
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
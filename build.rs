@@ -0,0 +1,6 @@
+fn main() {
+    // `Saturating` is newer than the minimum supported Rust version
+    let mut ac = autocfg::new();
+    ac.set_no_std(true);
+    ac.emit_path_cfg("core::num::Saturating", "has_saturating");
+}
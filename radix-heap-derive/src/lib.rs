@@ -0,0 +1,166 @@
+//! Derive macro for the `Radix` trait of
+//! [`radix-heap`](https://docs.rs/radix-heap).
+//!
+//! Use it through the `derive` feature of `radix-heap`, which re-exports it
+//! as `radix_heap::Radix`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Field, Index, Member};
+
+/// Derives `Radix` for a struct, comparing its fields in declaration order.
+///
+/// The radix similarity of two values is the sum of the similarities of
+/// their fields, up to and including the first field that is not equal, like
+/// for tuples. `RADIX_BITS` is the sum of the `RADIX_BITS` of the fields.
+///
+/// For this to be consistent with the `Ord` impl of the struct, it must
+/// compare the fields lexicographically in the same order, which is what
+/// `#[derive(Ord)]` does.
+///
+/// The fields can be annotated with:
+///
+/// - `#[radix(skip)]`: The field does not contribute to the similarity, and
+///   need not implement `Radix`. Keys that differ only in skipped fields
+///   are treated as equal, so the field must not affect `Ord` either, or such
+///   keys are popped in an arbitrary order.
+/// - `#[radix(reverse)]`: The field is compared in reverse by `Ord`, like
+///   `Reverse<T>`. Reversing the order does not change which high bits two
+///   values share, so the field contributes to the similarity like
+///   `Reverse<T>` does, which is the same as the field itself.
+///
+/// # Example
+///
+/// ```
+/// use radix_heap::{Radix, RadixHeapMap};
+///
+/// #[derive(Radix, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+/// struct Key {
+///     cost: u32,
+///     depth: u16,
+/// }
+///
+/// assert_eq!(Key::RADIX_BITS, 48);
+///
+/// let mut heap = RadixHeapMap::new();
+/// heap.push(Key { cost: 2, depth: 3 }, 'a');
+/// heap.push(Key { cost: 5, depth: 1 }, 'b');
+/// heap.push(Key { cost: 2, depth: 7 }, 'c');
+///
+/// assert_eq!(heap.pop(), Some((Key { cost: 5, depth: 1 }, 'b')));
+/// assert_eq!(heap.pop(), Some((Key { cost: 2, depth: 7 }, 'c')));
+/// assert_eq!(heap.pop(), Some((Key { cost: 2, depth: 3 }, 'a')));
+/// ```
+#[proc_macro_derive(Radix, attributes(radix))]
+pub fn derive_radix(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Radix can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut members = Vec::new();
+    let mut types = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        if !is_skipped(field)? {
+            members.push(match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(i)),
+            });
+            types.push(field.ty.clone());
+        }
+    }
+
+    let where_clause = input.generics.make_where_clause();
+    for ty in &types {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::radix_heap::Radix));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let radix_similarity = if members.is_empty() {
+        quote! {
+            #[inline]
+            fn radix_similarity(&self, _: &Self) -> u32 {
+                0
+            }
+        }
+    } else {
+        quote! {
+            #[inline]
+            fn radix_similarity(&self, other: &Self) -> u32 {
+                let mut similarity = 0;
+
+                #(
+                    let s = ::radix_heap::Radix::radix_similarity(&self.#members, &other.#members);
+                    similarity += s;
+                    if s < <#types as ::radix_heap::Radix>::RADIX_BITS {
+                        return similarity;
+                    }
+                )*
+
+                similarity
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::radix_heap::Radix for #name #ty_generics #where_clause {
+            #radix_similarity
+
+            const RADIX_BITS: u32 = 0 #(+ <#types as ::radix_heap::Radix>::RADIX_BITS)*;
+        }
+    })
+}
+
+/// Parses the `radix` attributes of a field, returning whether it is
+/// skipped.
+fn is_skipped(field: &Field) -> syn::Result<bool> {
+    let mut skip = false;
+    let mut reverse = false;
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("radix") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else if meta.path.is_ident("reverse") {
+                reverse = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `skip` or `reverse`"))
+            }
+        })?;
+    }
+
+    if skip && reverse {
+        return Err(syn::Error::new_spanned(
+            field,
+            "a field cannot be both skipped and reversed",
+        ));
+    }
+
+    Ok(skip)
+}
@@ -0,0 +1,152 @@
+extern crate quickcheck;
+
+use std::cmp::Reverse;
+
+use quickcheck::quickcheck;
+use radix_heap::{Radix, RadixHeapMap};
+
+#[derive(Radix, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Named {
+    a: u8,
+    b: i16,
+    c: u32,
+}
+
+#[derive(Radix, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Unnamed(u16, Reverse<u8>);
+
+#[derive(Radix, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Generic<T>(T, u8);
+
+#[derive(Radix)]
+struct Empty;
+
+/// A key with a field that is not part of the order.
+#[derive(Radix, Clone, Copy, Debug)]
+struct Skipped {
+    key: u32,
+    #[radix(skip)]
+    tag: char,
+}
+
+impl PartialEq for Skipped {
+    fn eq(&self, other: &Skipped) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Skipped {}
+
+impl PartialOrd for Skipped {
+    fn partial_cmp(&self, other: &Skipped) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Skipped {
+    fn cmp(&self, other: &Skipped) -> std::cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// A key whose second field is ordered in reverse.
+#[derive(Radix, Clone, Copy, Debug, PartialEq, Eq)]
+struct Reversed {
+    a: u8,
+    #[radix(reverse)]
+    b: u8,
+}
+
+impl PartialOrd for Reversed {
+    fn partial_cmp(&self, other: &Reversed) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Reversed {
+    fn cmp(&self, other: &Reversed) -> std::cmp::Ordering {
+        (self.a, Reverse(self.b)).cmp(&(other.a, Reverse(other.b)))
+    }
+}
+
+#[test]
+fn radix_bits() {
+    assert_eq!(Named::RADIX_BITS, 56);
+    assert_eq!(Unnamed::RADIX_BITS, 24);
+    assert_eq!(Generic::<u64>::RADIX_BITS, 72);
+    assert_eq!(Empty::RADIX_BITS, 0);
+    assert_eq!(Skipped::RADIX_BITS, 32);
+    assert_eq!(Reversed::RADIX_BITS, 16);
+}
+
+#[test]
+fn matches_tuples() {
+    fn prop(x: (u8, i16, u32), y: (u8, i16, u32)) -> bool {
+        let a = Named {
+            a: x.0,
+            b: x.1,
+            c: x.2,
+        };
+        let b = Named {
+            a: y.0,
+            b: y.1,
+            c: y.2,
+        };
+
+        a.radix_similarity(&b) == x.radix_similarity(&y)
+    }
+
+    quickcheck(prop as fn((u8, i16, u32), (u8, i16, u32)) -> bool);
+}
+
+#[test]
+fn skip() {
+    let a = Skipped { key: 3, tag: 'a' };
+    let b = Skipped { key: 3, tag: 'b' };
+    assert_eq!(a.radix_distance(&b), 0);
+
+    let mut heap = RadixHeapMap::new();
+    heap.push(a, ());
+    heap.push(Skipped { key: 1, tag: 'c' }, ());
+    heap.push(b, ());
+
+    let tags: Vec<_> = heap.into_iter_sorted().map(|(k, ())| k.tag).collect();
+    assert_eq!(tags, ['b', 'a', 'c']);
+}
+
+#[test]
+fn sort() {
+    fn prop<T: Ord + Radix + Copy>(mut xs: Vec<T>) -> bool {
+        let heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, ())).collect();
+
+        xs.sort();
+        xs.into_iter()
+            .rev()
+            .eq(heap.into_iter_sorted().map(|(k, ())| k))
+    }
+
+    fn named(xs: Vec<(u8, i16, u32)>) -> bool {
+        prop(xs.into_iter().map(|(a, b, c)| Named { a, b, c }).collect())
+    }
+
+    fn unnamed(xs: Vec<(u16, u8)>) -> bool {
+        prop(
+            xs.into_iter()
+                .map(|(a, b)| Unnamed(a, Reverse(b)))
+                .collect(),
+        )
+    }
+
+    fn generic(xs: Vec<(i64, u8)>) -> bool {
+        prop(xs.into_iter().map(|(a, b)| Generic(a, b)).collect())
+    }
+
+    fn reversed(xs: Vec<(u8, u8)>) -> bool {
+        prop(xs.into_iter().map(|(a, b)| Reversed { a, b }).collect())
+    }
+
+    quickcheck(named as fn(Vec<(u8, i16, u32)>) -> bool);
+    quickcheck(unnamed as fn(Vec<(u16, u8)>) -> bool);
+    quickcheck(generic as fn(Vec<(i64, u8)>) -> bool);
+    quickcheck(reversed as fn(Vec<(u8, u8)>) -> bool);
+}
@@ -0,0 +1,183 @@
+//! The allocator the buckets of a [`RadixHeapMap`](crate::RadixHeapMap) are
+//! stored in.
+//!
+//! With the `allocator-api2` feature, this is the `Allocator` trait of the
+//! [`allocator-api2`](https://docs.rs/allocator-api2) crate, so that any
+//! allocator implementing it can be used on stable Rust. Otherwise, only the
+//! global allocator is available, and the buckets are plain vectors.
+
+#[cfg(feature = "allocator-api2")]
+pub use allocator_api2::alloc::{Allocator, Global};
+
+#[cfg(feature = "allocator-api2")]
+pub(crate) use allocator_api2::vec::{Drain, IntoIter, Vec};
+
+#[cfg(not(feature = "allocator-api2"))]
+pub use self::global::{Allocator, Global};
+
+#[cfg(not(feature = "allocator-api2"))]
+pub(crate) use self::global::{Drain, IntoIter, Vec};
+
+/// Stand-ins for the parts of `allocator-api2` used by the heap, supporting
+/// only the global allocator.
+#[cfg(not(feature = "allocator-api2"))]
+mod global {
+    use core::{
+        iter::FusedIterator,
+        marker::PhantomData,
+        ops::{Deref, DerefMut, RangeBounds},
+    };
+
+    /// An allocator the items of a heap can be stored in.
+    ///
+    /// Enable the `allocator-api2` feature to use other allocators than
+    /// [`Global`].
+    pub trait Allocator: private::Sealed {}
+
+    /// The global memory allocator.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Global;
+
+    impl Allocator for Global {}
+
+    mod private {
+        pub trait Sealed {}
+
+        impl Sealed for super::Global {}
+    }
+
+    /// A vector in the global allocator with the interface of
+    /// `allocator_api2::vec::Vec`.
+    #[derive(Clone, Debug)]
+    pub(crate) struct Vec<T, A: Allocator = Global> {
+        vec: alloc::vec::Vec<T>,
+        alloc: A,
+    }
+
+    impl<T> Vec<T> {
+        pub(crate) fn new() -> Vec<T> {
+            Vec::new_in(Global)
+        }
+    }
+
+    impl<T, A: Allocator> Vec<T, A> {
+        pub(crate) fn new_in(alloc: A) -> Vec<T, A> {
+            Vec {
+                vec: alloc::vec::Vec::new(),
+                alloc,
+            }
+        }
+
+        pub(crate) fn allocator(&self) -> &A {
+            &self.alloc
+        }
+
+        pub(crate) fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, A> {
+            Drain {
+                drain: self.vec.drain(range),
+                alloc: PhantomData,
+            }
+        }
+    }
+
+    impl<T, A: Allocator> Deref for Vec<T, A> {
+        type Target = alloc::vec::Vec<T>;
+
+        fn deref(&self) -> &alloc::vec::Vec<T> {
+            &self.vec
+        }
+    }
+
+    impl<T, A: Allocator> DerefMut for Vec<T, A> {
+        fn deref_mut(&mut self) -> &mut alloc::vec::Vec<T> {
+            &mut self.vec
+        }
+    }
+
+    impl<T, A: Allocator> AsRef<[T]> for Vec<T, A> {
+        fn as_ref(&self) -> &[T] {
+            &self.vec
+        }
+    }
+
+    impl<T, A: Allocator> AsMut<[T]> for Vec<T, A> {
+        fn as_mut(&mut self) -> &mut [T] {
+            &mut self.vec
+        }
+    }
+
+    impl<T, A: Allocator> IntoIterator for Vec<T, A> {
+        type Item = T;
+        type IntoIter = IntoIter<T, A>;
+
+        fn into_iter(self) -> IntoIter<T, A> {
+            IntoIter {
+                iter: self.vec.into_iter(),
+                alloc: PhantomData,
+            }
+        }
+    }
+
+    impl<'a, T, A: Allocator> IntoIterator for &'a Vec<T, A> {
+        type Item = &'a T;
+        type IntoIter = core::slice::Iter<'a, T>;
+
+        fn into_iter(self) -> core::slice::Iter<'a, T> {
+            self.vec.iter()
+        }
+    }
+
+    impl<'a, T, A: Allocator> IntoIterator for &'a mut Vec<T, A> {
+        type Item = &'a mut T;
+        type IntoIter = core::slice::IterMut<'a, T>;
+
+        fn into_iter(self) -> core::slice::IterMut<'a, T> {
+            self.vec.iter_mut()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub(crate) struct IntoIter<T, A: Allocator = Global> {
+        iter: alloc::vec::IntoIter<T>,
+        alloc: PhantomData<A>,
+    }
+
+    impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+        type Item = T;
+
+        #[inline]
+        fn next(&mut self) -> Option<T> {
+            self.iter.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.iter.size_hint()
+        }
+    }
+
+    impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {}
+
+    impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
+
+    pub(crate) struct Drain<'a, T, A: Allocator = Global> {
+        drain: alloc::vec::Drain<'a, T>,
+        alloc: PhantomData<&'a A>,
+    }
+
+    impl<'a, T, A: Allocator> Iterator for Drain<'a, T, A> {
+        type Item = T;
+
+        #[inline]
+        fn next(&mut self) -> Option<T> {
+            self.drain.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.drain.size_hint()
+        }
+    }
+
+    impl<'a, T, A: Allocator> ExactSizeIterator for Drain<'a, T, A> {}
+
+    impl<'a, T, A: Allocator> FusedIterator for Drain<'a, T, A> {}
+}
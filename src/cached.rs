@@ -0,0 +1,58 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// An index into a bucket that is found lazily, such as the position of the
+/// item a radix heap pops next.
+///
+/// It can be stored through a shared reference, so that repeated peeks find
+/// it without scanning the bucket again. An atomic is used instead of a
+/// `Cell` to keep the heap `Sync`. Any two writes for the same state of the
+/// heap store the same index, so relaxed ordering is enough.
+pub(crate) struct CachedIndex(AtomicUsize);
+
+const UNKNOWN: usize = usize::MAX;
+
+impl CachedIndex {
+    pub(crate) fn new() -> CachedIndex {
+        CachedIndex(AtomicUsize::new(UNKNOWN))
+    }
+
+    #[inline]
+    pub(crate) fn get(&self) -> Option<usize> {
+        Some(self.0.load(Ordering::Relaxed)).filter(|&index| index != UNKNOWN)
+    }
+
+    #[inline]
+    pub(crate) fn set(&self, index: usize) {
+        self.0.store(index, Ordering::Relaxed);
+    }
+
+    /// Forgets the index, which must be done whenever items are moved.
+    #[inline]
+    pub(crate) fn forget(&mut self) {
+        *self.0.get_mut() = UNKNOWN;
+    }
+}
+
+impl Clone for CachedIndex {
+    fn clone(&self) -> CachedIndex {
+        CachedIndex(AtomicUsize::new(self.0.load(Ordering::Relaxed)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachedIndex;
+
+    #[test]
+    fn get_set() {
+        let mut index = CachedIndex::new();
+        assert_eq!(index.get(), None);
+
+        index.set(3);
+        assert_eq!(index.get(), Some(3));
+        assert_eq!(index.clone().get(), Some(3));
+
+        index.forget();
+        assert_eq!(index.get(), None);
+    }
+}
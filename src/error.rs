@@ -0,0 +1,147 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// The error returned when pushing a key that would break the monotonicity of
+/// a radix heap, i.e. a key ordered before the current top key.
+///
+/// The rejected key and value can be recovered from the error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MonotonicityError<K, V> {
+    pub(crate) key: K,
+    pub(crate) value: V,
+    pub(crate) top: K,
+}
+
+impl<K: Copy, V> MonotonicityError<K, V> {
+    /// The key that was rejected.
+    pub fn key(&self) -> K {
+        self.key
+    }
+
+    /// The value that was rejected.
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// The top key of the heap at the time the key was rejected.
+    pub fn top(&self) -> K {
+        self.top
+    }
+
+    /// Returns the rejected key-value pair.
+    pub fn into_inner(self) -> (K, V) {
+        (self.key, self.value)
+    }
+}
+
+impl<K, V> fmt::Display for MonotonicityError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("key is ordered before the current top key")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug, V: fmt::Debug> Error for MonotonicityError<K, V> {}
+
+/// The error returned when pushing onto a full
+/// [`FixedRadixHeapMap`](crate::FixedRadixHeapMap).
+///
+/// The rejected key and value can be recovered from the error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapacityError<K, V> {
+    pub(crate) key: K,
+    pub(crate) value: V,
+}
+
+impl<K: Copy, V> CapacityError<K, V> {
+    /// The key that was rejected.
+    pub fn key(&self) -> K {
+        self.key
+    }
+
+    /// The value that was rejected.
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Returns the rejected key-value pair.
+    pub fn into_inner(self) -> (K, V) {
+        (self.key, self.value)
+    }
+}
+
+impl<K, V> fmt::Display for CapacityError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("heap is full")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug, V: fmt::Debug> Error for CapacityError<K, V> {}
+
+/// The error returned by `try_extend` when an item is rejected.
+///
+/// All items before the rejected one have been pushed onto the heap. The
+/// items after it are dropped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TryExtendError<K, V> {
+    pub(crate) accepted: usize,
+    pub(crate) error: MonotonicityError<K, V>,
+}
+
+impl<K, V> TryExtendError<K, V> {
+    /// The number of items pushed onto the heap before the rejected one.
+    pub fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    /// The error for the rejected item.
+    pub fn error(&self) -> &MonotonicityError<K, V> {
+        &self.error
+    }
+
+    /// Returns the error for the rejected item.
+    pub fn into_error(self) -> MonotonicityError<K, V> {
+        self.error
+    }
+}
+
+impl<K, V> fmt::Display for TryExtendError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} after {} accepted items", self.error, self.accepted)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug, V: fmt::Debug> Error for TryExtendError<K, V> {}
+
+/// The error returned by `try_reserve` when the allocation fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TryReserveError(pub(crate) ());
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for TryReserveError {}
+
+/// The error returned when converting a time too far from the epoch into a
+/// [`Timestamp`](crate::Timestamp).
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimestampRangeError(pub(crate) ());
+
+#[cfg(feature = "std")]
+impl fmt::Display for TimestampRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("timestamp out of range")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for TimestampRangeError {}
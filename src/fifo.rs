@@ -0,0 +1,236 @@
+use core::{fmt, iter::FromIterator, mem};
+
+use crate::{Bucket, Max, Min, MonotonicityError, Order, Radix, RadixHeapMap};
+
+/// A montone priority queue implemented using a radix heap, which pops equal
+/// keys in first-in-first-out order.
+///
+/// This behaves like a [`RadixHeapMap`], except that if there is a tie
+/// between multiple elements, the first inserted element is popped first.
+/// This is useful when the order of equal keys must be deterministic, such as
+/// for events in a simulation.
+///
+/// This will be a max-heap by default. See [`RadixHeapMap`] for how to use a
+/// min-heap instead.
+#[derive(Clone)]
+pub struct FifoRadixHeapMap<K, V, O = Max> {
+    map: RadixHeapMap<K, V, O>,
+
+    /// Items taken from the first bucket of `map` in reverse insertion
+    /// order, so that the oldest one is at the end.
+    ///
+    /// All of these have a key equal to the top key, and were inserted before
+    /// anything currently in the first bucket of `map`.
+    ready: Bucket<K, V>,
+}
+
+impl<K: Radix + Ord + Copy, V> FifoRadixHeapMap<K, V> {
+    /// Create an empty `FifoRadixHeapMap`
+    pub fn new() -> FifoRadixHeapMap<K, V> {
+        FifoRadixHeapMap::from_map(RadixHeapMap::new())
+    }
+
+    /// Create an empty `FifoRadixHeapMap` with the top key set to a specific
+    /// value.
+    pub fn new_at(top: K) -> FifoRadixHeapMap<K, V> {
+        FifoRadixHeapMap::from_map(RadixHeapMap::new_at(top))
+    }
+}
+
+impl<K: Radix + Ord + Copy, V> FifoRadixHeapMap<K, V, Min> {
+    /// Create an empty min-ordered `FifoRadixHeapMap`
+    pub fn new_min() -> FifoRadixHeapMap<K, V, Min> {
+        FifoRadixHeapMap::from_map(RadixHeapMap::new_min())
+    }
+
+    /// Create an empty min-ordered `FifoRadixHeapMap` with the top key set to
+    /// a specific value.
+    pub fn new_min_at(top: K) -> FifoRadixHeapMap<K, V, Min> {
+        FifoRadixHeapMap::from_map(RadixHeapMap::new_min_at(top))
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, O: Order> FifoRadixHeapMap<K, V, O> {
+    fn from_map(map: RadixHeapMap<K, V, O>) -> FifoRadixHeapMap<K, V, O> {
+        FifoRadixHeapMap {
+            map,
+            ready: Bucket::new(),
+        }
+    }
+
+    /// Drops all items from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.ready.clear();
+    }
+
+    /// Drop all items from the heap and sets the top key to a specific value.
+    pub fn clear_to(&mut self, top: K) {
+        self.map.clear_to(top);
+        self.ready.clear();
+    }
+
+    /// Pushes a new key value pair onto the heap.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key, or smaller for a
+    /// min-heap.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        self.map.push(key, value);
+    }
+
+    /// Pushes a new key value pair onto the heap, or returns an error if the
+    /// key is larger than the current top key, or smaller for a min-heap.
+    #[inline]
+    pub fn try_push(&mut self, key: K, value: V) -> Result<(), MonotonicityError<K, V>> {
+        self.map.try_push(key, value)
+    }
+
+    /// Remove the greatest element from the heap and returns it, or `None` if
+    /// empty. For a min-heap, the least element is removed instead.
+    ///
+    /// If there is a tie between multiple elements, the first inserted
+    /// element will be popped first.
+    ///
+    /// This will set the top key to the extracted key.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.ready.is_empty() {
+            if self.map.buckets[0].is_empty() {
+                self.map.constrain();
+            }
+
+            // The first bucket is in insertion order, so reversing it once
+            // lets every item in it be popped from the end.
+            mem::swap(&mut self.ready, &mut self.map.buckets[0]);
+            self.ready.reverse();
+            self.map.len -= self.ready.len();
+            self.map.occupied.remove(0);
+            self.map.greatest.forget();
+        }
+
+        self.ready.pop()
+    }
+
+    /// Returns the number of elements in the heap
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len() + self.ready.len()
+    }
+
+    /// Returns true if there is no elements in the heap
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The current top value. All keys pushed onto the heap must be smaller than this value, or
+    /// greater for a min-heap.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.map.top()
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, O: Order> Default for FifoRadixHeapMap<K, V, O> {
+    fn default() -> FifoRadixHeapMap<K, V, O> {
+        FifoRadixHeapMap::from_map(RadixHeapMap::default())
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, O: Order> FromIterator<(K, V)> for FifoRadixHeapMap<K, V, O> {
+    fn from_iter<I>(iter: I) -> FifoRadixHeapMap<K, V, O>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        FifoRadixHeapMap::from_map(iter.into_iter().collect())
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, O: Order> Extend<(K, V)> for FifoRadixHeapMap<K, V, O> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.map.extend(iter);
+    }
+}
+
+impl<K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug, O: Order> fmt::Debug
+    for FifoRadixHeapMap<K, V, O>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.ready.iter().chain(self.map.iter()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::FifoRadixHeapMap;
+    use crate::{Radix, RadixHeapMap};
+    use std::cmp::Reverse;
+    use std::{vec, vec::Vec};
+
+    #[test]
+    fn tie_order() {
+        let input = [(1u32, 'a'), (2, 'b'), (1, 'c'), (2, 'd'), (1, 'e')];
+
+        let mut lifo: RadixHeapMap<_, _> = input.iter().copied().collect();
+        let mut fifo: FifoRadixHeapMap<_, _> = input.iter().copied().collect();
+
+        assert_eq!(lifo.pop(), Some((2, 'd')));
+        assert_eq!(fifo.pop(), Some((2, 'b')));
+
+        lifo.push(2, 'f');
+        fifo.push(2, 'f');
+
+        let lifo: Vec<_> = std::iter::from_fn(|| lifo.pop()).collect();
+        let fifo: Vec<_> = std::iter::from_fn(|| fifo.pop()).collect();
+
+        assert_eq!(lifo, vec![(2, 'f'), (2, 'b'), (1, 'e'), (1, 'c'), (1, 'a')]);
+        assert_eq!(fifo, vec![(2, 'd'), (2, 'f'), (1, 'a'), (1, 'c'), (1, 'e')]);
+    }
+
+    #[test]
+    fn min_tie_order() {
+        let mut heap = FifoRadixHeapMap::new_min();
+        heap.extend(vec![(3u32, 'a'), (1, 'b'), (3, 'c'), (1, 'd')]);
+
+        assert_eq!(heap.len(), 4);
+        assert_eq!(heap.pop(), Some((1, 'b')));
+        heap.push(1, 'e');
+        assert_eq!(heap.pop(), Some((1, 'd')));
+        assert_eq!(heap.pop(), Some((1, 'e')));
+        assert_eq!(heap.pop(), Some((3, 'a')));
+        assert_eq!(heap.pop(), Some((3, 'c')));
+        assert!(heap.pop().is_none());
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn stable_sort() {
+        fn prop<T: Ord + Radix + Copy>(xs: Vec<T>) -> bool {
+            let mut heap: FifoRadixHeapMap<_, _> =
+                xs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+
+            let mut expected: Vec<_> = xs.into_iter().enumerate().map(|(i, d)| (d, i)).collect();
+            expected.sort_by_key(|&(d, _)| Reverse(d));
+
+            heap.len() == expected.len()
+                && std::iter::from_fn(|| heap.pop()).eq(expected)
+                && heap.is_empty()
+        }
+
+        quickcheck(prop as fn(Vec<()>) -> bool);
+        quickcheck(prop as fn(Vec<u8>) -> bool);
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+        quickcheck(prop as fn(Vec<(u8, u16)>) -> bool);
+    }
+}
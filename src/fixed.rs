@@ -0,0 +1,392 @@
+use core::{cmp::Ordering, fmt, marker::PhantomData};
+
+use crate::{occupancy::Occupancy, CapacityError, Max, Min, Order, Radix};
+
+/// The most buckets a `FixedRadixHeapMap` can have: one for each of up to
+/// 128 radix bits, one for keys equal to the top key, and the bucket of
+/// initial entries.
+const MAX_BUCKETS: usize = 130;
+
+/// A montone priority queue implemented using a radix heap, which stores up
+/// to `N` items inline and never allocates.
+///
+/// This behaves like a [`RadixHeapMap`], including the order in which ties
+/// are popped, except that pushing onto a full heap returns an error. This
+/// makes it usable where allocation is not possible, such as in interrupt
+/// handlers.
+///
+/// The buckets are linked lists threaded through the inline array of items,
+/// so no space is reserved per bucket. Keys can have at most 128 radix bits.
+/// Using a key with more is a compile-time error.
+///
+/// This will be a max-heap by default. See [`RadixHeapMap`] for how to use a
+/// min-heap instead.
+///
+/// [`RadixHeapMap`]: crate::RadixHeapMap
+#[derive(Clone)]
+pub struct FixedRadixHeapMap<K, V, const N: usize, O = Max> {
+    len: usize,
+
+    /// The current top key, or none if one is not set yet.
+    top: Option<K>,
+
+    /// The items, with `None` in the vacant slots.
+    items: [Option<(K, V)>; N],
+
+    /// The slot following each slot in its bucket, or in the free list if
+    /// the slot is vacant.
+    next: [Option<usize>; N],
+
+    /// The first slot of each of the K::RADIX_BITS + 1 buckets, followed by
+    /// the bucket of initial entries before a top key is found.
+    ///
+    /// Items are linked in at the front of a bucket, so the most recently
+    /// pushed item comes first.
+    heads: [Option<usize>; MAX_BUCKETS],
+
+    /// Which of `heads` are non-empty.
+    occupied: Occupancy<[u64; MAX_BUCKETS / 64 + 1]>,
+
+    /// The first vacant slot.
+    free: Option<usize>,
+
+    order: PhantomData<O>,
+}
+
+impl<K: Radix + Ord + Copy, V, const N: usize> FixedRadixHeapMap<K, V, N> {
+    /// Create an empty `FixedRadixHeapMap`
+    pub fn new() -> FixedRadixHeapMap<K, V, N> {
+        FixedRadixHeapMap::with_top(None)
+    }
+
+    /// Create an empty `FixedRadixHeapMap` with the top key set to a specific
+    /// value.
+    pub fn new_at(top: K) -> FixedRadixHeapMap<K, V, N> {
+        FixedRadixHeapMap::with_top(Some(top))
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, const N: usize> FixedRadixHeapMap<K, V, N, Min> {
+    /// Create an empty min-ordered `FixedRadixHeapMap`
+    pub fn new_min() -> FixedRadixHeapMap<K, V, N, Min> {
+        FixedRadixHeapMap::with_top(None)
+    }
+
+    /// Create an empty min-ordered `FixedRadixHeapMap` with the top key set
+    /// to a specific value.
+    pub fn new_min_at(top: K) -> FixedRadixHeapMap<K, V, N, Min> {
+        FixedRadixHeapMap::with_top(Some(top))
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, const N: usize, O: Order> FixedRadixHeapMap<K, V, N, O> {
+    /// The index of the bucket of initial entries.
+    ///
+    /// Evaluating this fails to compile if the key has too many radix bits.
+    const INITIAL: usize = {
+        assert!(
+            K::RADIX_BITS as usize + 2 <= MAX_BUCKETS,
+            "Key must have at most 128 radix bits"
+        );
+        K::RADIX_BITS as usize + 1
+    };
+
+    fn with_top(top: Option<K>) -> FixedRadixHeapMap<K, V, N, O> {
+        let mut heap = FixedRadixHeapMap {
+            len: 0,
+            top,
+            items: [(); N].map(|_| None),
+            next: [None; N],
+            heads: [None; MAX_BUCKETS],
+            occupied: Occupancy::fixed(),
+            free: None,
+            order: PhantomData,
+        };
+
+        heap.clear_items();
+        heap
+    }
+
+    /// Drops all items from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.clear_items();
+        self.top = None;
+    }
+
+    /// Drop all items from the heap and sets the top key to a specific value.
+    pub fn clear_to(&mut self, top: K) {
+        self.clear_items();
+        self.top = Some(top);
+    }
+
+    /// Sets the top value to the current maximum key value in the heap, or
+    /// the minimum for a min-heap.
+    pub fn constrain(&mut self) {
+        let index = if self.top.is_some() {
+            match self.occupied.first() {
+                None | Some(0) => return,
+                Some(index) => index,
+            }
+        } else if self.heads[Self::INITIAL].is_some() {
+            Self::INITIAL
+        } else {
+            return;
+        };
+
+        self.occupied.remove(index);
+
+        // Reverse the bucket while finding the new top key, so that linking
+        // the items in at the front of their new buckets keeps the most
+        // recently pushed ones first.
+        let mut head = self.heads[index].take();
+        let mut reversed = None;
+        let mut top = None;
+
+        while let Some(slot) = head {
+            head = self.next[slot];
+            self.next[slot] = reversed;
+            reversed = Some(slot);
+
+            let key = self.key(slot);
+
+            if top.map_or(true, |top| O::compare(&key, &top) == Ordering::Greater) {
+                top = Some(key);
+            }
+        }
+
+        let top = top.expect("Expected non-empty bucket");
+        self.top = Some(top);
+
+        while let Some(slot) = reversed {
+            reversed = self.next[slot];
+            let bucket = self.key(slot).radix_distance(&top) as usize;
+            self.link(slot, bucket);
+        }
+    }
+
+    /// Pushes a new key value pair onto the heap, or returns an error if the
+    /// heap is full.
+    ///
+    /// The rejected key and value can be recovered from the error.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key, or smaller for a
+    /// min-heap.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) -> Result<(), CapacityError<K, V>> {
+        let bucket = if let Some(top) = self.top {
+            assert!(
+                O::compare(&key, &top) != Ordering::Greater,
+                "Key must not be ordered before the current top key"
+            );
+            key.radix_distance(&top) as usize
+        } else {
+            Self::INITIAL
+        };
+
+        let slot = match self.free {
+            Some(slot) => slot,
+            None => return Err(CapacityError { key, value }),
+        };
+
+        self.free = self.next[slot];
+        self.items[slot] = Some((key, value));
+        self.link(slot, bucket);
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Remove the greatest element from the heap and returns it, or `None` if
+    /// empty. For a min-heap, the least element is removed instead.
+    ///
+    /// If there is a tie between multiple elements, the last inserted element
+    /// will be popped first.
+    ///
+    /// This will set the top key to the extracted key.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.heads[0].is_none() {
+            self.constrain();
+        }
+
+        let slot = self.heads[0]?;
+        self.heads[0] = self.next[slot];
+
+        if self.heads[0].is_none() {
+            self.occupied.remove(0);
+        }
+
+        self.next[slot] = self.free;
+        self.free = Some(slot);
+        self.len -= 1;
+
+        self.items[slot].take()
+    }
+
+    /// Returns the number of elements in the heap
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there is no elements in the heap
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if no more elements can be pushed onto the heap
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Returns the number of elements the heap can hold, which is `N`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// The current top value. All keys pushed onto the heap must be smaller than this value, or
+    /// greater for a min-heap.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.top
+    }
+
+    /// Drops all items and links every slot into the free list, without
+    /// changing the top key.
+    fn clear_items(&mut self) {
+        self.len = 0;
+
+        for (slot, (item, next)) in self.items.iter_mut().zip(&mut self.next).enumerate() {
+            *item = None;
+            *next = Some(slot + 1).filter(|&next| next < N);
+        }
+
+        self.heads[..=Self::INITIAL].fill(None);
+        self.occupied.clear();
+        self.free = Some(0).filter(|_| N > 0);
+    }
+
+    #[inline]
+    fn key(&self, slot: usize) -> K {
+        self.items[slot].as_ref().expect("Expected occupied slot").0
+    }
+
+    /// Links an occupied slot in at the front of a bucket.
+    #[inline]
+    fn link(&mut self, slot: usize, bucket: usize) {
+        self.next[slot] = self.heads[bucket];
+        self.heads[bucket] = Some(slot);
+        self.occupied.insert(bucket);
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, const N: usize, O: Order> Default for FixedRadixHeapMap<K, V, N, O> {
+    fn default() -> FixedRadixHeapMap<K, V, N, O> {
+        FixedRadixHeapMap::with_top(None)
+    }
+}
+
+impl<K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug, const N: usize, O: Order> fmt::Debug
+    for FixedRadixHeapMap<K, V, N, O>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.items.iter().flatten()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::FixedRadixHeapMap;
+    use crate::{Min, Radix, RadixHeapMap};
+    use std::vec::Vec;
+
+    #[test]
+    fn push_pop() {
+        let mut heap = FixedRadixHeapMap::<u32, char, 3>::new();
+        assert_eq!(heap.push(0, 'a'), Ok(()));
+        assert_eq!(heap.push(3, 'b'), Ok(()));
+        assert_eq!(heap.push(2, 'c'), Ok(()));
+
+        assert!(heap.is_full());
+        let error = heap.push(1, 'd').unwrap_err();
+        assert_eq!(error.into_inner(), (1, 'd'));
+
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.pop(), Some((3, 'b')));
+        assert_eq!(heap.push(1, 'd'), Ok(()));
+        assert_eq!(heap.pop(), Some((2, 'c')));
+        assert_eq!(heap.pop(), Some((1, 'd')));
+        assert_eq!(heap.pop(), Some((0, 'a')));
+        assert!(heap.pop().is_none());
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_pop_panic() {
+        let mut heap = FixedRadixHeapMap::<u32, char, 4>::new();
+        heap.push(0, 'a').unwrap();
+        heap.push(3, 'b').unwrap();
+
+        assert_eq!(heap.pop(), Some((3, 'b')));
+        let _ = heap.push(4, 'd');
+    }
+
+    #[test]
+    fn min_push_pop() {
+        let mut heap = FixedRadixHeapMap::<u32, char, 4, Min>::new_min_at(1);
+        heap.push(5, 'a').unwrap();
+        heap.push(2, 'b').unwrap();
+
+        assert_eq!(heap.pop(), Some((2, 'b')));
+        assert_eq!(heap.top(), Some(2));
+        heap.push(2, 'c').unwrap();
+        assert_eq!(heap.pop(), Some((2, 'c')));
+        assert_eq!(heap.pop(), Some((5, 'a')));
+        assert!(heap.pop().is_none());
+    }
+
+    #[test]
+    fn empty() {
+        let mut heap = FixedRadixHeapMap::<u32, (), 0>::new();
+        assert!(heap.is_full());
+        assert!(heap.push(0, ()).is_err());
+        assert!(heap.pop().is_none());
+    }
+
+    #[test]
+    fn matches_radix_heap_map() {
+        fn prop<T: Ord + Radix + Copy>(xs: Vec<T>) -> bool {
+            let xs = &xs[..xs.len().min(64)];
+
+            let mut heap = FixedRadixHeapMap::<_, _, 64>::new();
+            let mut expected = RadixHeapMap::new();
+
+            for (i, &x) in xs.iter().enumerate() {
+                if heap.push(x, i).is_err() {
+                    return false;
+                }
+
+                expected.push(x, i);
+            }
+
+            heap.len() == xs.len()
+                && std::iter::from_fn(|| heap.pop()).eq(std::iter::from_fn(|| expected.pop()))
+                && heap.is_empty()
+        }
+
+        quickcheck(prop as fn(Vec<()>) -> bool);
+        quickcheck(prop as fn(Vec<u8>) -> bool);
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+        quickcheck(prop as fn(Vec<u128>) -> bool);
+        quickcheck(prop as fn(Vec<(u8, u16)>) -> bool);
+    }
+}
@@ -0,0 +1,351 @@
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use crate::Radix;
+
+macro_rules! total_float {
+    ($(#[$meta:meta])* $name:ident, $f:ty, $bits:ty) => {
+        $(#[$meta])*
+        ///
+        /// Keys are ordered like `total_cmp`: negative NaNs first, then
+        /// negative infinity, the negative numbers, `-0.0`, `0.0`, the
+        /// positive numbers, positive infinity and positive NaNs last. Unlike
+        /// with `NotNan` from the `ordered-float` crate, any float can be
+        /// used as a key.
+        #[derive(Clone, Copy, Debug, Default)]
+        pub struct $name(pub $f);
+
+        impl $name {
+            /// The bits of the float, mapped such that they are ordered like
+            /// the float when compared as unsigned integers.
+            ///
+            /// The sign bit is flipped for positive floats, and all bits are
+            /// flipped for negative ones, since they are stored as sign and
+            /// magnitude.
+            #[inline]
+            fn key(self) -> $bits {
+                const SIGN: $bits = 1 << (<$bits>::BITS - 1);
+
+                let bits = self.0.to_bits();
+                if bits & SIGN == 0 {
+                    bits | SIGN
+                } else {
+                    !bits
+                }
+            }
+        }
+
+        impl From<$f> for $name {
+            #[inline]
+            fn from(value: $f) -> $name {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for $f {
+            #[inline]
+            fn from(value: $name) -> $f {
+                value.0
+            }
+        }
+
+        impl PartialEq for $name {
+            #[inline]
+            fn eq(&self, other: &$name) -> bool {
+                self.key() == other.key()
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialOrd for $name {
+            #[inline]
+            fn partial_cmp(&self, other: &$name) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            #[inline]
+            fn cmp(&self, other: &$name) -> Ordering {
+                self.key().cmp(&other.key())
+            }
+        }
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.key().hash(state)
+            }
+        }
+
+        impl Radix for $name {
+            #[inline]
+            fn radix_similarity(&self, other: &$name) -> u32 {
+                self.key().radix_similarity(&other.key())
+            }
+
+            const RADIX_BITS: u32 = <$bits>::RADIX_BITS;
+        }
+    };
+}
+
+total_float! {
+    /// An `f32` key with a total order, including NaN.
+    TotalF32, f32, u32
+}
+
+total_float! {
+    /// An `f64` key with a total order, including NaN.
+    TotalF64, f64, u64
+}
+
+#[cfg(any(
+    feature = "ordered-float",
+    feature = "ordered-float-3",
+    feature = "ordered-float-4",
+    feature = "ordered-float-5"
+))]
+mod ordered {
+    //! `Radix` impls for the types of the `ordered-float` crate, for each of
+    //! its versions enabled by a feature.
+
+    use super::{TotalF32, TotalF64};
+    use crate::Radix;
+
+    /// Maps a float to bits ordered like the `ordered-float` crate orders it.
+    ///
+    /// The zeros are equal there, so they are mapped to the same bits, or
+    /// keys equal to the top key could end up in different buckets. NaNs are
+    /// equal to each other and greater than every other float, so they are
+    /// all mapped to the greatest bits.
+    trait OrderedBits {
+        type Bits: Radix;
+
+        fn ordered_bits(self) -> Self::Bits;
+    }
+
+    macro_rules! ordered_bits_impl {
+        ($f:ty, $bits:ty, $total:ident) => {
+            impl OrderedBits for $f {
+                type Bits = $bits;
+
+                #[inline]
+                fn ordered_bits(self) -> $bits {
+                    if self.is_nan() {
+                        <$bits>::MAX
+                    } else if self == 0.0 {
+                        $total(0.0).key()
+                    } else {
+                        $total(self).key()
+                    }
+                }
+            }
+        };
+    }
+
+    ordered_bits_impl!(f32, u32, TotalF32);
+    ordered_bits_impl!(f64, u64, TotalF64);
+
+    /// Implements `Radix` for the `NotNan` and `OrderedFloat` types of a
+    /// version of the `ordered-float` crate.
+    macro_rules! radix_ordered_float_impl {
+        ($ordered_float:ident) => {
+            radix_ordered_float_impl!($ordered_float::NotNan<f32>, f32);
+            radix_ordered_float_impl!($ordered_float::NotNan<f64>, f64);
+            radix_ordered_float_impl!($ordered_float::OrderedFloat<f32>, f32);
+            radix_ordered_float_impl!($ordered_float::OrderedFloat<f64>, f64);
+        };
+        ($wrapper:ty, $f:ty) => {
+            impl Radix for $wrapper {
+                #[inline]
+                fn radix_similarity(&self, other: &$wrapper) -> u32 {
+                    let self_bits = self.into_inner().ordered_bits();
+                    let other_bits = other.into_inner().ordered_bits();
+                    self_bits.radix_similarity(&other_bits)
+                }
+
+                const RADIX_BITS: u32 = <<$f as OrderedBits>::Bits as Radix>::RADIX_BITS;
+            }
+        };
+    }
+
+    #[cfg(feature = "ordered-float")]
+    radix_ordered_float_impl!(ordered_float);
+
+    #[cfg(feature = "ordered-float-3")]
+    radix_ordered_float_impl!(ordered_float_3);
+
+    #[cfg(feature = "ordered-float-4")]
+    radix_ordered_float_impl!(ordered_float_4);
+
+    #[cfg(feature = "ordered-float-5")]
+    radix_ordered_float_impl!(ordered_float_5);
+
+    #[cfg(test)]
+    mod tests {
+        extern crate quickcheck;
+
+        /// Tests the `Radix` impls for a version of the `ordered-float` crate.
+        macro_rules! ordered_float_tests {
+            ($name:ident, $ordered_float:ident) => {
+                mod $name {
+                    use super::quickcheck::quickcheck;
+                    use crate::RadixHeapMap;
+                    use std::vec::Vec;
+                    use $ordered_float::{NotNan, OrderedFloat};
+
+                    #[test]
+                    fn zeros_are_equal() {
+                        // Both zeros must be popped before any negative float
+                        let mut heap = RadixHeapMap::new();
+                        heap.push(NotNan::new(-0.0f64).unwrap(), 'a');
+                        assert_eq!(heap.pop().map(|(_, v)| v), Some('a'));
+                        heap.push(NotNan::new(-f64::MIN_POSITIVE).unwrap(), 'b');
+                        heap.push(NotNan::new(0.0).unwrap(), 'c');
+                        assert_eq!(heap.pop().map(|(_, v)| v), Some('c'));
+                        assert_eq!(heap.pop().map(|(_, v)| v), Some('b'));
+
+                        let mut heap = RadixHeapMap::new();
+                        heap.push(OrderedFloat(0.0f32), 'a');
+                        assert_eq!(heap.pop().map(|(_, v)| v), Some('a'));
+                        heap.push(OrderedFloat(-f32::MIN_POSITIVE), 'b');
+                        heap.push(OrderedFloat(-0.0), 'c');
+                        assert_eq!(heap.pop().map(|(_, v)| v), Some('c'));
+                        assert_eq!(heap.pop().map(|(_, v)| v), Some('b'));
+                    }
+
+                    #[test]
+                    fn nan_pops_first() {
+                        let mut heap = RadixHeapMap::new();
+                        heap.push(OrderedFloat(f64::INFINITY), 'a');
+                        heap.push(OrderedFloat(-f64::NAN), 'b');
+                        heap.push(OrderedFloat(1.0), 'c');
+                        heap.push(OrderedFloat(f64::NAN), 'd');
+
+                        let values: Vec<_> = heap.into_iter_sorted().map(|(_, v)| v).collect();
+                        assert_eq!(values, ['d', 'b', 'a', 'c']);
+                    }
+
+                    #[test]
+                    fn sort() {
+                        fn prop(xs: Vec<f32>) -> bool {
+                            let mut xs: Vec<_> = xs.into_iter().map(OrderedFloat).collect();
+                            let heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, ())).collect();
+
+                            xs.sort();
+                            xs.into_iter()
+                                .rev()
+                                .eq(heap.into_iter_sorted().map(|(k, ())| k))
+                        }
+
+                        quickcheck(prop as fn(Vec<f32>) -> bool);
+                    }
+                }
+            };
+        }
+
+        #[cfg(feature = "ordered-float")]
+        ordered_float_tests!(ordered_float_2, ordered_float);
+
+        #[cfg(feature = "ordered-float-3")]
+        ordered_float_tests!(ordered_float_3, ordered_float_3);
+
+        #[cfg(feature = "ordered-float-4")]
+        ordered_float_tests!(ordered_float_4, ordered_float_4);
+
+        #[cfg(feature = "ordered-float-5")]
+        ordered_float_tests!(ordered_float_5, ordered_float_5);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::{quickcheck, TestResult};
+    use super::{TotalF32, TotalF64};
+    use crate::{Radix, RadixHeapMap, RadixMinHeapMap};
+    use std::vec::Vec;
+
+    #[test]
+    fn order() {
+        let keys = [
+            -f64::NAN,
+            f64::NEG_INFINITY,
+            -1e300,
+            -1.0,
+            -f64::MIN_POSITIVE,
+            -0.0,
+            0.0,
+            f64::MIN_POSITIVE,
+            1.0,
+            1e300,
+            f64::INFINITY,
+            f64::NAN,
+        ];
+
+        for (i, &a) in keys.iter().enumerate() {
+            for (j, &b) in keys.iter().enumerate() {
+                assert_eq!(TotalF64(a).cmp(&TotalF64(b)), i.cmp(&j), "{} {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_partial_cmp() {
+        fn prop(a: f32, b: f32) -> TestResult {
+            // NaNs and zeros are the only floats not ordered by `partial_cmp`
+            // like their bits
+            if a.is_nan() || b.is_nan() || (a == 0.0 && b == 0.0) {
+                return TestResult::discard();
+            }
+
+            TestResult::from_bool(Some(TotalF32(a).cmp(&TotalF32(b))) == a.partial_cmp(&b))
+        }
+
+        quickcheck(prop as fn(f32, f32) -> TestResult);
+    }
+
+    #[test]
+    fn negative_radix_dist() {
+        assert_eq!(TotalF64(-1.0).radix_distance(&TotalF64(-1.0)), 0);
+        assert_eq!(TotalF64(-1.0).radix_distance(&TotalF64(1.0)), 64);
+        assert_eq!(
+            TotalF64(-2.0).radix_distance(&TotalF64(-1.0)),
+            TotalF64(1.0).radix_distance(&TotalF64(2.0))
+        );
+    }
+
+    #[test]
+    fn sort() {
+        fn prop<T: Ord + Radix + Copy>(mut xs: Vec<T>) -> bool {
+            let heap: RadixHeapMap<_, _> = xs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+            let min_heap: RadixMinHeapMap<_, _> =
+                xs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+
+            xs.sort();
+
+            xs.iter()
+                .rev()
+                .copied()
+                .eq(heap.into_iter_sorted().map(|(k, _)| k))
+                && xs
+                    .iter()
+                    .copied()
+                    .eq(min_heap.into_iter_sorted().map(|(k, _)| k))
+        }
+
+        fn prop_f32(xs: Vec<f32>) -> bool {
+            prop(xs.into_iter().map(TotalF32).collect())
+        }
+
+        fn prop_f64(xs: Vec<f64>) -> bool {
+            prop(xs.into_iter().map(TotalF64).collect())
+        }
+
+        quickcheck(prop_f32 as fn(Vec<f32>) -> bool);
+        quickcheck(prop_f64 as fn(Vec<f64>) -> bool);
+    }
+}
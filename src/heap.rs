@@ -0,0 +1,307 @@
+//! A radix heap storing only keys.
+
+use core::{fmt, iter::FromIterator, iter::FusedIterator};
+
+use crate::{Radix, RadixHeapMap};
+
+/// A montone priority queue of keys implemented using a radix heap.
+///
+/// This will be a max-heap. It behaves like a [`RadixHeapMap`] without
+/// values and shares its implementation.
+///
+/// See the [module documentation](../index.html) for more information.
+///
+/// It is a logic error for a key to be modified in such a way that the
+/// item's ordering relative to any other item, as determined by the `Ord`
+/// trait, changes while it is in the heap. This is normally only possible
+/// through `Cell`, `RefCell`, global state, I/O, or unsafe code.
+#[derive(Clone)]
+pub struct RadixHeap<K> {
+    map: RadixHeapMap<K, ()>,
+}
+
+impl<K: Radix + Ord + Copy> RadixHeap<K> {
+    /// Create an empty `RadixHeap`
+    pub fn new() -> RadixHeap<K> {
+        RadixHeap {
+            map: RadixHeapMap::new(),
+        }
+    }
+
+    /// Create an empty `RadixHeap` with the top key set to a specific value.
+    ///
+    /// This can be more efficient if you have a known minimum bound of the
+    /// items being pushed to the heap.
+    pub fn new_at(top: K) -> RadixHeap<K> {
+        RadixHeap {
+            map: RadixHeapMap::new_at(top),
+        }
+    }
+
+    /// Drops all items from the `RadixHeap` and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Drop all items from the `RadixHeap` and sets the top key to a specific
+    /// value.
+    ///
+    /// This can be more efficient if you have a known maximum bound of the
+    /// items being pushed to the heap.
+    pub fn clear_to(&mut self, top: K) {
+        self.map.clear_to(top);
+    }
+
+    /// Sets the top value to the current maximum key value in the heap
+    pub fn constrain(&mut self) {
+        self.map.constrain();
+    }
+
+    /// Pushes a new key onto the heap.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    #[inline]
+    pub fn push(&mut self, key: K) {
+        self.map.push(key, ());
+    }
+
+    /// Remove the greatest key from the heap and returns it, or `None` if
+    /// empty.
+    ///
+    /// This will set the top key to the extracted key.
+    #[inline]
+    pub fn pop(&mut self) -> Option<K> {
+        self.map.pop().map(|(k, ())| k)
+    }
+
+    /// Returns the greatest key in the heap without removing it, or `None` if
+    /// empty.
+    ///
+    /// Unlike `pop`, this does not change the top key.
+    #[inline]
+    pub fn peek(&self) -> Option<K> {
+        self.map.peek().map(|(k, ())| k)
+    }
+
+    /// Returns the number of elements in the heap
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if there is no elements in the heap
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// The current top value. All keys pushed onto the heap must be smaller than this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.map.top()
+    }
+
+    /// Discards as much additional capacity as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.map.shrink_to_fit();
+    }
+
+    /// Returns an iterator of all keys in the RadixHeap in arbitrary order
+    pub fn iter(&self) -> Iter<'_, K> {
+        Iter(self.map.keys())
+    }
+}
+
+impl<K: Radix + Ord + Copy> Default for RadixHeap<K> {
+    fn default() -> RadixHeap<K> {
+        RadixHeap::new()
+    }
+}
+
+impl<K: Radix + Ord + Copy> FromIterator<K> for RadixHeap<K> {
+    fn from_iter<I>(iter: I) -> RadixHeap<K>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        RadixHeap {
+            map: iter.into_iter().map(|k| (k, ())).collect(),
+        }
+    }
+}
+
+impl<K: Radix + Ord + Copy> Extend<K> for RadixHeap<K> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.map.extend(iter.into_iter().map(|k| (k, ())));
+    }
+}
+
+impl<'a, K: Radix + Ord + Copy + 'a> Extend<&'a K> for RadixHeap<K> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a K>,
+    {
+        self.map.extend(iter.into_iter().map(|&k| (k, ())));
+    }
+}
+
+impl<K: Radix + Ord + Copy + fmt::Debug> fmt::Debug for RadixHeap<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// An owning iterator over keys in a RadixHeap.
+#[derive(Clone)]
+pub struct IntoIter<K>(crate::IntoIter<K, ()>);
+
+impl<K> Iterator for IntoIter<K> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, ())| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn for_each<F>(self, mut f: F)
+    where
+        F: FnMut(Self::Item),
+    {
+        self.0.for_each(|(k, ())| f(k))
+    }
+}
+
+impl<K> ExactSizeIterator for IntoIter<K> {}
+
+impl<K> FusedIterator for IntoIter<K> {}
+
+/// An iterator over keys in a RadixHeap.
+#[derive(Clone)]
+pub struct Iter<'a, K>(crate::Keys<'a, K, ()>);
+
+impl<'a, K> Iterator for Iter<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn for_each<F>(self, f: F)
+    where
+        F: FnMut(Self::Item),
+    {
+        self.0.for_each(f)
+    }
+}
+
+impl<'a, K> ExactSizeIterator for Iter<'a, K> {}
+
+impl<'a, K> FusedIterator for Iter<'a, K> {}
+
+impl<K: Radix + Ord + Copy> IntoIterator for RadixHeap<K> {
+    type Item = K;
+    type IntoIter = IntoIter<K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.map.into_iter())
+    }
+}
+
+impl<'a, K: Radix + Ord + Copy> IntoIterator for &'a RadixHeap<K> {
+    type Item = &'a K;
+    type IntoIter = Iter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::RadixHeap;
+    use crate::Radix;
+    use std::{vec, vec::Vec};
+
+    #[test]
+    fn push_pop() {
+        let mut heap = RadixHeap::new();
+        heap.push(0u32);
+        heap.push(3);
+        heap.push(2);
+
+        assert!(heap.len() == 3);
+        assert!(!heap.is_empty());
+
+        assert!(heap.pop() == Some(3));
+        assert!(heap.top() == Some(3));
+        assert!(heap.pop() == Some(2));
+        assert!(heap.pop() == Some(0));
+        assert!(heap.pop().is_none());
+
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_pop_panic() {
+        let mut heap = RadixHeap::new();
+        heap.push(0u32);
+        heap.push(3);
+
+        assert!(heap.pop() == Some(3));
+        heap.push(4);
+    }
+
+    #[test]
+    fn sort() {
+        fn prop<T: Ord + Radix + Copy>(mut xs: Vec<T>) -> bool {
+            let mut heap: RadixHeap<_> = xs.iter().copied().collect();
+
+            xs.sort();
+
+            while xs.pop() == heap.pop() {
+                if xs.is_empty() {
+                    return true;
+                }
+            }
+
+            false
+        }
+
+        quickcheck(prop as fn(Vec<()>) -> bool);
+        quickcheck(prop as fn(Vec<u32>) -> bool);
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+        quickcheck(prop as fn(Vec<(u32, i32)>) -> bool);
+        quickcheck(prop as fn(Vec<u8>) -> bool);
+        quickcheck(prop as fn(Vec<i16>) -> bool);
+        quickcheck(prop as fn(Vec<(i64, usize)>) -> bool);
+        quickcheck(prop as fn(Vec<i128>) -> bool);
+        quickcheck(prop as fn(Vec<u128>) -> bool);
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut heap = RadixHeap::new();
+        heap.extend(&[1, 5, 7]);
+
+        assert_eq!(Some(7), heap.pop());
+
+        let mut vec: Vec<_> = heap.into_iter().collect();
+        vec.sort();
+        assert_eq!(vec, vec![1, 5]);
+    }
+}
@@ -0,0 +1,526 @@
+//! An addressable radix heap supporting key updates and removal.
+
+use alloc::vec::Vec;
+use core::{cmp::Ordering, fmt, marker::PhantomData, mem};
+
+use crate::{occupancy::Occupancy, Max, Min, Order, Radix};
+
+/// A handle to an item in an [`IndexedRadixHeapMap`], returned by
+/// [`IndexedRadixHeapMap::push`].
+///
+/// A handle stays valid until its item is popped or removed. Using it after
+/// that is not an error; the heap will simply not find the item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: u32,
+}
+
+#[derive(Clone)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+
+    /// The bucket the item is in and its index inside that bucket.
+    bucket: usize,
+    index: usize,
+}
+
+#[derive(Clone)]
+struct Slot<K, V> {
+    /// Incremented every time the slot is vacated, so that handles to
+    /// previous items in the slot are rejected.
+    generation: u32,
+    entry: Option<Entry<K, V>>,
+}
+
+/// A montone priority queue implemented using a radix heap, where items can
+/// be addressed through handles.
+///
+/// Pushing an item returns a [`Handle`] which can later be used to look up,
+/// remove or change the key of the item without searching for it. This makes
+/// it possible to implement eg. Dijkstra's algorithm without pushing
+/// duplicate items.
+///
+/// This will be a max-heap by default, like [`RadixHeapMap`]. Unlike
+/// `RadixHeapMap`, the order in which ties are popped is unspecified.
+///
+/// [`RadixHeapMap`]: crate::RadixHeapMap
+#[derive(Clone)]
+pub struct IndexedRadixHeapMap<K, V, O = Max> {
+    len: usize,
+
+    /// The current top key, or none if one is not set yet.
+    top: Option<K>,
+
+    /// The K::RADIX_BITS + 1 number of buckets the items can land in,
+    /// followed by the bucket of initial entries before a top key is found.
+    ///
+    /// Buckets hold slot indices rather than the items themselves, so items
+    /// never move in memory and removal only needs a swap within a bucket.
+    buckets: Vec<Vec<usize>>,
+
+    /// Which of `buckets` are non-empty.
+    occupied: Occupancy,
+
+    slots: Vec<Slot<K, V>>,
+
+    /// The indices of the vacant slots.
+    free: Vec<usize>,
+
+    order: PhantomData<O>,
+}
+
+impl<K: Radix + Ord + Copy, V> IndexedRadixHeapMap<K, V> {
+    /// Create an empty `IndexedRadixHeapMap`
+    pub fn new() -> IndexedRadixHeapMap<K, V> {
+        IndexedRadixHeapMap::with_top(None)
+    }
+
+    /// Create an empty `IndexedRadixHeapMap` with the top key set to a
+    /// specific value.
+    pub fn new_at(top: K) -> IndexedRadixHeapMap<K, V> {
+        IndexedRadixHeapMap::with_top(Some(top))
+    }
+}
+
+impl<K: Radix + Ord + Copy, V> IndexedRadixHeapMap<K, V, Min> {
+    /// Create an empty min-ordered `IndexedRadixHeapMap`
+    pub fn new_min() -> IndexedRadixHeapMap<K, V, Min> {
+        IndexedRadixHeapMap::with_top(None)
+    }
+
+    /// Create an empty min-ordered `IndexedRadixHeapMap` with the top key set
+    /// to a specific value.
+    pub fn new_min_at(top: K) -> IndexedRadixHeapMap<K, V, Min> {
+        IndexedRadixHeapMap::with_top(Some(top))
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, O: Order> IndexedRadixHeapMap<K, V, O> {
+    fn with_top(top: Option<K>) -> IndexedRadixHeapMap<K, V, O> {
+        IndexedRadixHeapMap {
+            len: 0,
+            top,
+            buckets: (0..=K::RADIX_BITS + 1).map(|_| Vec::new()).collect(),
+            occupied: Occupancy::new(K::RADIX_BITS as usize + 2),
+            slots: Vec::new(),
+            free: Vec::new(),
+            order: PhantomData,
+        }
+    }
+
+    /// The index of the bucket of initial entries.
+    #[inline]
+    fn initial() -> usize {
+        K::RADIX_BITS as usize + 1
+    }
+
+    /// Drops all items from the heap and sets the top key to `None`.
+    ///
+    /// All handles to the dropped items are invalidated.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.top = None;
+
+        for bucket in &mut self.buckets {
+            bucket.clear();
+        }
+
+        self.occupied.clear();
+        self.free.clear();
+
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            if slot.entry.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+            }
+
+            self.free.push(i);
+        }
+    }
+
+    /// Sets the top value to the current maximum key value in the heap, or
+    /// the minimum for a min-heap.
+    pub fn constrain(&mut self) {
+        // The initial bucket is always empty once a top key is set, so the
+        // first non-empty bucket is never the initial one here.
+        let index = if self.top.is_some() {
+            match self.occupied.first() {
+                None | Some(0) => return,
+                Some(index) => index,
+            }
+        } else if !self.buckets[Self::initial()].is_empty() {
+            Self::initial()
+        } else {
+            return;
+        };
+
+        let mut repush = mem::take(&mut self.buckets[index]);
+        self.occupied.remove(index);
+
+        let top = repush
+            .iter()
+            .map(|&slot| self.entry(slot).key)
+            .max_by(|a, b| O::compare(a, b))
+            .expect("Expected non-empty bucket");
+
+        self.top = Some(top);
+
+        for slot in repush.drain(..) {
+            let bucket = self.entry(slot).key.radix_distance(&top) as usize;
+            self.link(slot, bucket);
+        }
+
+        // Put the emptied bucket back to keep its allocation
+        self.buckets[index] = repush;
+    }
+
+    /// Pushes a new key value pair onto the heap and returns a handle to it.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key, or smaller for a
+    /// min-heap.
+    pub fn push(&mut self, key: K, value: V) -> Handle {
+        let bucket = self.bucket_for(key);
+
+        let entry = Entry {
+            key,
+            value,
+            bucket,
+            index: 0,
+        };
+
+        let slot = if let Some(slot) = self.free.pop() {
+            self.slots[slot].entry = Some(entry);
+            slot
+        } else {
+            self.slots.push(Slot {
+                generation: 0,
+                entry: Some(entry),
+            });
+            self.slots.len() - 1
+        };
+
+        self.link(slot, bucket);
+        self.len += 1;
+
+        Handle {
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    /// Remove the greatest element from the heap and returns it, or `None` if
+    /// empty. For a min-heap, the least element is removed instead.
+    ///
+    /// This will set the top key to the extracted key.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.buckets[0].is_empty() {
+            self.constrain();
+        }
+
+        let slot = self.buckets[0].pop()?;
+
+        if self.buckets[0].is_empty() {
+            self.occupied.remove(0);
+        }
+
+        Some(self.release(slot))
+    }
+
+    /// Returns the key and a reference to the value of the item with the
+    /// given handle, or `None` if it is no longer in the heap.
+    pub fn get(&self, handle: Handle) -> Option<(K, &V)> {
+        let slot = self.slots.get(handle.slot)?;
+
+        match &slot.entry {
+            Some(entry) if slot.generation == handle.generation => Some((entry.key, &entry.value)),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the value of the item with the given
+    /// handle, or `None` if it is no longer in the heap.
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut V> {
+        let slot = self.slots.get_mut(handle.slot)?;
+
+        match &mut slot.entry {
+            Some(entry) if slot.generation == handle.generation => Some(&mut entry.value),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the item with the given handle is still in the heap.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    /// Changes the key of the item with the given handle and returns the old
+    /// key, or returns `None` if the item is no longer in the heap.
+    ///
+    /// This moves the item directly to the bucket of the new key in `O(1)`.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the new key is larger than the current top key, or smaller
+    /// for a min-heap.
+    pub fn update_key(&mut self, handle: Handle, key: K) -> Option<K> {
+        let (old_key, _) = self.get(handle)?;
+        let bucket = self.bucket_for(key);
+
+        self.unlink(handle.slot);
+        self.entry_mut(handle.slot).key = key;
+        self.link(handle.slot, bucket);
+
+        Some(old_key)
+    }
+
+    /// Removes the item with the given handle from the heap and returns it,
+    /// or `None` if it is no longer in the heap.
+    ///
+    /// The top key is left unchanged.
+    pub fn remove(&mut self, handle: Handle) -> Option<(K, V)> {
+        self.get(handle)?;
+        self.unlink(handle.slot);
+        Some(self.release(handle.slot))
+    }
+
+    /// Returns the number of elements in the heap
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there is no elements in the heap
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The current top value. All keys pushed onto the heap must be smaller than this value, or
+    /// greater for a min-heap.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.top
+    }
+
+    /// Returns the bucket a key should be placed in.
+    fn bucket_for(&self, key: K) -> usize {
+        if let Some(top) = self.top {
+            assert!(
+                O::compare(&key, &top) != Ordering::Greater,
+                "Key must not be ordered before the current top key"
+            );
+            key.radix_distance(&top) as usize
+        } else {
+            Self::initial()
+        }
+    }
+
+    fn entry(&self, slot: usize) -> &Entry<K, V> {
+        self.slots[slot]
+            .entry
+            .as_ref()
+            .expect("Expected occupied slot")
+    }
+
+    fn entry_mut(&mut self, slot: usize) -> &mut Entry<K, V> {
+        self.slots[slot]
+            .entry
+            .as_mut()
+            .expect("Expected occupied slot")
+    }
+
+    /// Adds an occupied slot to the end of a bucket.
+    fn link(&mut self, slot: usize, bucket: usize) {
+        let index = self.buckets[bucket].len();
+        self.buckets[bucket].push(slot);
+        self.occupied.insert(bucket);
+
+        let entry = self.entry_mut(slot);
+        entry.bucket = bucket;
+        entry.index = index;
+    }
+
+    /// Removes an occupied slot from its bucket by swapping it with the last
+    /// slot of the bucket.
+    fn unlink(&mut self, slot: usize) {
+        let entry = self.entry(slot);
+        let (bucket, index) = (entry.bucket, entry.index);
+
+        self.buckets[bucket].swap_remove(index);
+
+        if let Some(&moved) = self.buckets[bucket].get(index) {
+            self.entry_mut(moved).index = index;
+        } else if self.buckets[bucket].is_empty() {
+            self.occupied.remove(bucket);
+        }
+    }
+
+    /// Vacates a slot that has already been unlinked and returns its item.
+    fn release(&mut self, slot: usize) -> (K, V) {
+        let slot_ref = &mut self.slots[slot];
+        let entry = slot_ref.entry.take().expect("Expected occupied slot");
+        slot_ref.generation = slot_ref.generation.wrapping_add(1);
+
+        self.free.push(slot);
+        self.len -= 1;
+
+        (entry.key, entry.value)
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, O: Order> Default for IndexedRadixHeapMap<K, V, O> {
+    fn default() -> IndexedRadixHeapMap<K, V, O> {
+        IndexedRadixHeapMap::with_top(None)
+    }
+}
+
+impl<K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug, O: Order> fmt::Debug
+    for IndexedRadixHeapMap<K, V, O>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.slots
+                    .iter()
+                    .filter_map(|slot| slot.entry.as_ref())
+                    .map(|entry| (&entry.key, &entry.value)),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::IndexedRadixHeapMap;
+    use std::vec::Vec;
+
+    #[test]
+    fn push_pop() {
+        let mut heap = IndexedRadixHeapMap::new();
+        heap.push(0u32, 'a');
+        heap.push(3, 'b');
+        heap.push(2, 'c');
+
+        assert!(heap.len() == 3);
+        assert!(heap.pop() == Some((3, 'b')));
+        assert!(heap.pop() == Some((2, 'c')));
+        assert!(heap.pop() == Some((0, 'a')));
+        assert!(heap.pop().is_none());
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_pop_panic() {
+        let mut heap = IndexedRadixHeapMap::new();
+        heap.push(0u32, 'a');
+        heap.push(3, 'b');
+
+        assert!(heap.pop() == Some((3, 'b')));
+        heap.push(4, 'd');
+    }
+
+    #[test]
+    fn update_key() {
+        let mut heap = IndexedRadixHeapMap::new_min();
+        let a = heap.push(10u32, 'a');
+        let b = heap.push(20, 'b');
+        let c = heap.push(30, 'c');
+
+        assert_eq!(heap.pop(), Some((10, 'a')));
+        assert!(!heap.contains(a));
+        assert_eq!(heap.update_key(a, 11), None);
+
+        assert_eq!(heap.update_key(c, 15), Some(30));
+        assert_eq!(heap.get(c), Some((15, &'c')));
+        assert_eq!(heap.pop(), Some((15, 'c')));
+        assert_eq!(heap.get(b), Some((20, &'b')));
+
+        *heap.get_mut(b).unwrap() = 'd';
+        assert_eq!(heap.pop(), Some((20, 'd')));
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn update_key_panic() {
+        let mut heap = IndexedRadixHeapMap::new_min();
+        heap.push(10u32, 'a');
+        let b = heap.push(20, 'b');
+
+        assert_eq!(heap.pop(), Some((10, 'a')));
+        heap.update_key(b, 9);
+    }
+
+    #[test]
+    fn remove() {
+        let mut heap = IndexedRadixHeapMap::new();
+        let a = heap.push(1u32, 'a');
+        let b = heap.push(5, 'b');
+        let c = heap.push(3, 'c');
+
+        assert_eq!(heap.remove(b), Some((5, 'b')));
+        assert_eq!(heap.remove(b), None);
+        assert_eq!(heap.len(), 2);
+
+        // The slot of the removed item is reused, but the old handle is not
+        let d = heap.push(4, 'd');
+        assert_eq!(heap.get(b), None);
+        assert_eq!(heap.get(d), Some((4, &'d')));
+
+        assert_eq!(heap.pop(), Some((4, 'd')));
+        assert_eq!(heap.remove(a), Some((1, 'a')));
+        assert_eq!(heap.top(), Some(4));
+        assert_eq!(heap.pop(), Some((3, 'c')));
+        assert!(!heap.contains(c));
+        assert!(heap.pop().is_none());
+    }
+
+    #[test]
+    fn sort_with_updates() {
+        fn prop(xs: Vec<(u32, Option<u32>, bool)>) -> bool {
+            let mut heap = IndexedRadixHeapMap::new();
+            let handles: Vec<_> = xs.iter().map(|&(k, _, _)| heap.push(k, ())).collect();
+
+            let mut expected: Vec<_> = xs.iter().map(|&(k, _, _)| k).collect();
+            expected.sort();
+
+            // Pop one item to set a top key, then update and remove the rest
+            let popped = heap.pop().map(|(k, ())| k);
+            if popped != expected.pop() {
+                return false;
+            }
+
+            let mut expected = Vec::new();
+
+            for (&(_, update, remove), &handle) in xs.iter().zip(&handles) {
+                if !heap.contains(handle) {
+                    continue;
+                }
+
+                if remove {
+                    heap.remove(handle);
+                } else if let Some(key) = update {
+                    let key = key.min(heap.top().unwrap());
+                    heap.update_key(handle, key);
+                    expected.push(key);
+                } else {
+                    expected.push(heap.get(handle).unwrap().0);
+                }
+            }
+
+            expected.sort();
+
+            heap.len() == expected.len()
+                && std::iter::from_fn(|| heap.pop().map(|(k, ())| k)).eq(expected.into_iter().rev())
+        }
+
+        quickcheck(prop as fn(Vec<(u32, Option<u32>, bool)>) -> bool);
+    }
+}
@@ -1,15 +1,80 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
-
-use std::{
-    cmp::Reverse, default::Default, fmt, iter::FromIterator, iter::FusedIterator, num::Wrapping,
+#![no_std]
+
+extern crate alloc;
+
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+use alloc::vec::Vec;
+use core::{
+    cmp::{Ordering, Reverse},
+    default::Default,
+    fmt,
+    iter::FromIterator,
+    iter::FusedIterator,
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    },
+    ops::{Deref, DerefMut},
 };
 
-type Bucket<K, V> = Vec<(K, V)>;
+#[cfg(has_saturating)]
+use core::num::Saturating;
+
+mod allocator;
+mod cached;
+mod error;
+mod fifo;
+mod fixed;
+mod float;
+pub mod heap;
+pub mod indexed;
+mod occupancy;
+mod order;
+mod packed;
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod time;
+
+pub use allocator::{Allocator, Global};
+#[cfg(feature = "std")]
+pub use error::TimestampRangeError;
+pub use error::{CapacityError, MonotonicityError, TryExtendError, TryReserveError};
+pub use fifo::FifoRadixHeapMap;
+pub use fixed::FixedRadixHeapMap;
+pub use float::{TotalF32, TotalF64};
+pub use heap::RadixHeap;
+pub use indexed::IndexedRadixHeapMap;
+pub use order::{Max, Min, Order};
+pub use packed::{PackedBits, PackedKey, PackedKeyBuilder, PackedKeyReader};
+#[cfg(feature = "derive")]
+pub use radix_heap_derive::Radix;
+#[cfg(feature = "rkyv")]
+pub use rkyv_impl::ArchivedRadixHeapMap;
+#[cfg(feature = "std")]
+pub use time::Timestamp;
+
+use cached::CachedIndex;
+use occupancy::Occupancy;
+
+type Bucket<K, V, A = Global> = allocator::Vec<(K, V), A>;
 
 /// A montone priority queue implemented using a radix heap.
 ///
-/// This will be a max-heap.
+/// This will be a max-heap by default. The order is given by the `O` type
+/// parameter, which is either [`Max`] or [`Min`]. See [`RadixMinHeapMap`] for
+/// a min-heap.
+///
+/// The items are stored in the allocator `A`, which is the global allocator
+/// by default. Enable the `allocator-api2` feature and use
+/// [`new_in`](RadixHeapMap::new_in) to store them in another allocator, such
+/// as an arena.
 ///
 /// See the [module documentation](index.html) for more information.
 ///
@@ -18,7 +83,7 @@ type Bucket<K, V> = Vec<(K, V)>;
 /// trait, changes while it is in the heap. This is normally only possible
 /// through `Cell`, `RefCell`, global state, I/O, or unsafe code.
 #[derive(Clone)]
-pub struct RadixHeapMap<K, V> {
+pub struct RadixHeapMap<K, V, O = Max, A: Allocator = Global> {
     len: usize,
 
     /// The current top key, or none if one is not set yet.
@@ -26,23 +91,36 @@ pub struct RadixHeapMap<K, V> {
 
     /// The K::RADIX_BITS + 1 number of buckets the items can land in.
     ///
-    /// TODO: when rust supports associated consts as array sizes, use a fixed
-    /// array instead of a vec.
-    buckets: Vec<Bucket<K, V>>,
+    /// TODO: use a fixed array instead of a vec once array lengths can be
+    /// derived from associated consts. Const generics alone are not enough,
+    /// as `[Bucket<K, V>; K::RADIX_BITS as usize + 1]` still requires the
+    /// unstable `generic_const_exprs` feature, and a separate const parameter
+    /// could not default to a value derived from `K`.
+    buckets: allocator::Vec<Bucket<K, V, A>, A>,
+
+    /// Which of `buckets` are non-empty.
+    occupied: Occupancy<allocator::Vec<u64, A>>,
 
     /// The initial entries before a top key is found.
-    initial: Bucket<K, V>,
+    initial: Bucket<K, V, A>,
+
+    /// The index of the item that is popped next in its bucket, once
+    /// `find_greatest` has scanned for it.
+    greatest: CachedIndex,
+
+    order: PhantomData<O>,
 }
 
+/// A montone min-priority queue implemented using a radix heap.
+///
+/// Keys are popped in increasing order, and all keys pushed onto the heap
+/// must be greater than or equal to the top key.
+pub type RadixMinHeapMap<K, V> = RadixHeapMap<K, V, Min>;
+
 impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
     /// Create an empty `RadixHeapMap`
     pub fn new() -> RadixHeapMap<K, V> {
-        RadixHeapMap {
-            len: 0,
-            top: None,
-            buckets: (0..=K::RADIX_BITS).map(|_| Bucket::default()).collect(),
-            initial: Bucket::default(),
-        }
+        RadixHeapMap::with_top_in(None, Global)
     }
 
     /// Create an empty `RadixHeapMap` with the top key set to a specific
@@ -51,23 +129,89 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
     /// This can be more efficient if you have a known minimum bound of the
     /// items being pushed to the heap.
     pub fn new_at(top: K) -> RadixHeapMap<K, V> {
+        RadixHeapMap::with_top_in(Some(top), Global)
+    }
+
+    /// Create an empty `RadixHeapMap` with space for at least `capacity`
+    /// items before its first pop, and an even share of them in each bucket.
+    ///
+    /// See [`reserve`](RadixHeapMap::reserve) for how the space is allocated.
+    pub fn with_capacity(capacity: usize) -> RadixHeapMap<K, V> {
+        let mut heap = RadixHeapMap::new();
+        heap.reserve(capacity);
+        heap
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, A: Allocator + Clone> RadixHeapMap<K, V, Max, A> {
+    /// Create an empty `RadixHeapMap` storing its items in the given
+    /// allocator.
+    pub fn new_in(alloc: A) -> RadixHeapMap<K, V, Max, A> {
+        RadixHeapMap::with_top_in(None, alloc)
+    }
+
+    /// Create an empty `RadixHeapMap` storing its items in the given
+    /// allocator, with the top key set to a specific value.
+    pub fn new_at_in(top: K, alloc: A) -> RadixHeapMap<K, V, Max, A> {
+        RadixHeapMap::with_top_in(Some(top), alloc)
+    }
+}
+
+impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V, Min> {
+    /// Create an empty `RadixMinHeapMap`
+    pub fn new_min() -> RadixMinHeapMap<K, V> {
+        RadixHeapMap::with_top_in(None, Global)
+    }
+
+    /// Create an empty `RadixMinHeapMap` with the top key set to a specific
+    /// value.
+    ///
+    /// This can be more efficient if you have a known minimum bound of the
+    /// items being pushed to the heap.
+    pub fn new_min_at(top: K) -> RadixMinHeapMap<K, V> {
+        RadixHeapMap::with_top_in(Some(top), Global)
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, A: Allocator + Clone> RadixHeapMap<K, V, Min, A> {
+    /// Create an empty `RadixMinHeapMap` storing its items in the given
+    /// allocator.
+    pub fn new_min_in(alloc: A) -> RadixHeapMap<K, V, Min, A> {
+        RadixHeapMap::with_top_in(None, alloc)
+    }
+
+    /// Create an empty `RadixMinHeapMap` storing its items in the given
+    /// allocator, with the top key set to a specific value.
+    pub fn new_min_at_in(top: K, alloc: A) -> RadixHeapMap<K, V, Min, A> {
+        RadixHeapMap::with_top_in(Some(top), alloc)
+    }
+}
+
+impl<K: Radix + Ord + Copy, V, O: Order, A: Allocator + Clone> RadixHeapMap<K, V, O, A> {
+    fn with_top_in(top: Option<K>, alloc: A) -> RadixHeapMap<K, V, O, A> {
+        let mut buckets = allocator::Vec::new_in(alloc.clone());
+
+        for _ in 0..=K::RADIX_BITS {
+            buckets.push(Bucket::new_in(alloc.clone()));
+        }
+
         RadixHeapMap {
             len: 0,
-            top: Some(top),
-            buckets: (0..=K::RADIX_BITS).map(|_| Bucket::default()).collect(),
-            initial: Bucket::default(),
+            top,
+            buckets,
+            occupied: Occupancy::new_in(K::RADIX_BITS as usize + 1, alloc.clone()),
+            initial: Bucket::new_in(alloc),
+            greatest: CachedIndex::new(),
+            order: PhantomData,
         }
     }
+}
 
+impl<K: Radix + Ord + Copy, V, O: Order, A: Allocator> RadixHeapMap<K, V, O, A> {
     /// Drops all items from the `RadixHeapMap` and sets the top key to `None`.
     pub fn clear(&mut self) {
-        self.len = 0;
+        self.clear_items();
         self.top = None;
-        self.initial.clear();
-
-        for bucket in &mut self.buckets {
-            bucket.clear();
-        }
     }
 
     /// Drop all items from the `RadixHeapMap` and sets the top key to a
@@ -80,14 +224,14 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
         self.top = Some(top);
     }
 
-    /// Sets the top value to the current maximum key value in the heap
+    /// Sets the top value to the current maximum key value in the heap, or
+    /// the minimum for a min-heap.
     pub fn constrain(&mut self) {
         let (buckets, repush) = if self.top.is_some() {
-            let index = self.buckets.iter().position(|bucket| !bucket.is_empty());
-
-            match index {
+            match self.occupied.first() {
                 None | Some(0) => return,
                 Some(index) => {
+                    self.occupied.remove(index);
                     let (buckets, rest) = self.buckets.split_at_mut(index);
                     (buckets, &mut rest[0])
                 }
@@ -98,46 +242,107 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
             return;
         };
 
-        let top = *repush
-            .iter()
-            .map(|(k, _)| k)
-            .max()
-            .expect("Expected non-empty bucket");
+        let top = match self.greatest.get() {
+            Some(index) => repush[index].0,
+            None => *repush
+                .iter()
+                .map(|(k, _)| k)
+                .max_by(|a, b| O::compare(a, b))
+                .expect("Expected non-empty bucket"),
+        };
 
         self.top = Some(top);
+        self.greatest.forget();
 
-        repush
-            .drain(..)
-            .for_each(|(key, value)| buckets[key.radix_distance(&top) as usize].push((key, value)));
+        let occupied = &mut self.occupied;
+
+        repush.drain(..).for_each(|(key, value)| {
+            let index = key.radix_distance(&top) as usize;
+            occupied.insert(index);
+            buckets[index].push((key, value));
+        });
     }
 
     /// Pushes a new key value pair onto the heap.
     ///
     /// Panics
     /// ------
-    /// Panics if the key is larger than the current top key.
+    /// Panics if the key is larger than the current top key, or smaller for a
+    /// min-heap.
     #[inline]
     pub fn push(&mut self, key: K, value: V) {
         let bucket = if let Some(top) = self.top {
-            assert!(key <= top, "Key must be lower or equal to current top key");
-            &mut self.buckets[key.radix_distance(&top) as usize]
+            assert!(
+                O::compare(&key, &top) != Ordering::Greater,
+                "Key must not be ordered before the current top key"
+            );
+            Some(key.radix_distance(&top) as usize)
         } else {
-            &mut self.initial
+            None
         };
 
-        bucket.push((key, value));
+        // A key that is not popped after the greatest one lands last in the
+        // first bucket, so it is the one popped next
+        if let Some(greatest) = self.peek_cached() {
+            if O::compare(&key, &greatest) != Ordering::Less {
+                self.greatest.set(self.bucket(bucket).len());
+            }
+        }
+
+        if let Some(index) = bucket {
+            self.occupied.insert(index);
+        }
+
+        self.bucket_mut(bucket).push((key, value));
         self.len += 1;
     }
 
+    /// Pushes a new key value pair onto the heap, or returns an error if the
+    /// key is larger than the current top key, or smaller for a min-heap.
+    ///
+    /// The rejected key and value can be recovered from the error.
+    #[inline]
+    pub fn try_push(&mut self, key: K, value: V) -> Result<(), MonotonicityError<K, V>> {
+        match self.top {
+            Some(top) if O::compare(&key, &top) == Ordering::Greater => {
+                Err(MonotonicityError { key, value, top })
+            }
+            _ => {
+                self.push(key, value);
+                Ok(())
+            }
+        }
+    }
+
+    /// Pushes all key value pairs of an iterator onto the heap, stopping at
+    /// the first key that `try_push` rejects.
+    ///
+    /// On error, the items before the rejected one remain in the heap and the
+    /// error reports how many of them were pushed.
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryExtendError<K, V>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        for (accepted, (k, v)) in iter.into_iter().enumerate() {
+            self.try_push(k, v)
+                .map_err(|error| TryExtendError { accepted, error })?;
+        }
+
+        Ok(())
+    }
+
     /// Remove the greatest element from the heap and returns it, or `None` if
-    /// empty.
+    /// empty. For a min-heap, the least element is removed instead.
     ///
     /// If there is a tie between multiple elements, the last inserted element
-    /// will be popped first.
+    /// will be popped first. Use [`FifoRadixHeapMap`] to pop the first inserted
+    /// element instead.
     ///
     /// This will set the top key to the extracted key.
     #[inline]
     pub fn pop(&mut self) -> Option<(K, V)> {
+        self.greatest.forget();
+
         let ret = self.buckets[0].pop().or_else(|| {
             self.constrain();
             self.buckets[0].pop()
@@ -145,11 +350,111 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
 
         if ret.is_some() {
             self.len -= 1;
+
+            if self.buckets[0].is_empty() {
+                self.occupied.remove(0);
+            }
         }
 
         ret
     }
 
+    /// Returns the greatest element in the heap without removing it, or
+    /// `None` if empty. For a min-heap, the least element is returned instead.
+    ///
+    /// Unlike `pop`, this does not change the top key, so any key that could
+    /// be pushed before peeking can still be pushed afterwards. If there is a
+    /// tie, the element that `pop` would return is the one returned.
+    ///
+    /// Unless an item equal to the top key is in the heap, this scans the
+    /// bucket the element is in, which holds every item before the first
+    /// pop and so takes O(n) time. The position found is remembered until
+    /// the heap is changed by anything but a push, so peeking again takes
+    /// constant time, and the next `pop` does not scan the bucket again.
+    #[inline]
+    pub fn peek(&self) -> Option<(K, &V)> {
+        let (bucket, index) = self.find_greatest()?;
+        let (key, value) = &self.bucket(bucket)[index];
+        Some((*key, value))
+    }
+
+    /// Returns a mutable guard to the greatest element in the heap, or `None`
+    /// if empty.
+    ///
+    /// The guard allows modifying the value in place and can be used to
+    /// conditionally pop the element with [`PeekMut::pop`]. Like `peek`, this
+    /// does not change the top key unless the element is popped, and takes
+    /// the same time.
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, K, V, O, A>> {
+        let (bucket, index) = self.find_greatest()?;
+
+        Some(PeekMut {
+            heap: self,
+            bucket,
+            index,
+        })
+    }
+
+    /// Finds the bucket and index of the item that will be popped next
+    /// without redistributing any items.
+    ///
+    /// The bucket is `None` if the item is in the initial bucket.
+    fn find_greatest(&self) -> Option<(Option<usize>, usize)> {
+        let bucket = if self.top.is_some() {
+            Some(self.occupied.first()?)
+        } else {
+            None
+        };
+
+        let items = self.bucket(bucket);
+
+        // Everything in the first bucket is equal to the top key, so the last
+        // one is popped first. Otherwise `max_by_key` picks the last of the
+        // greatest items, matching the order `constrain` will leave them in.
+        let index = if bucket == Some(0) {
+            items.len() - 1
+        } else if let Some(index) = self.greatest.get() {
+            index
+        } else {
+            let index = items
+                .iter()
+                .enumerate()
+                .max_by(|(_, (a, _)), (_, (b, _))| O::compare(a, b))?
+                .0;
+            self.greatest.set(index);
+            index
+        };
+
+        Some((bucket, index))
+    }
+
+    /// Returns the key of the item that is popped next, if its index is
+    /// cached.
+    fn peek_cached(&self) -> Option<K> {
+        let index = self.greatest.get()?;
+        let bucket = if self.top.is_some() {
+            Some(self.occupied.first()?)
+        } else {
+            None
+        };
+
+        Some(self.bucket(bucket)[index].0)
+    }
+
+    #[inline]
+    fn bucket(&self, bucket: Option<usize>) -> &Bucket<K, V, A> {
+        bucket.map_or(&self.initial, |i| &self.buckets[i])
+    }
+
+    #[inline]
+    fn bucket_mut(&mut self, bucket: Option<usize>) -> &mut Bucket<K, V, A> {
+        match bucket {
+            Some(i) => &mut self.buckets[i],
+            None => &mut self.initial,
+        }
+    }
+
     /// Returns the number of elements in the heap
     #[inline]
     pub fn len(&self) -> usize {
@@ -162,12 +467,138 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
         self.len() == 0
     }
 
-    /// The current top value. All keys pushed onto the heap must be smaller than this value.
+    /// The current top value. All keys pushed onto the heap must be smaller than this value, or
+    /// greater for a min-heap.
     #[inline]
     pub fn top(&self) -> Option<K> {
         self.top
     }
 
+    /// Returns a reference to the allocator the items are stored in.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.initial.allocator()
+    }
+
+    /// Returns the number of items the heap has allocated space for, summed
+    /// over the buckets that items can be pushed to.
+    ///
+    /// Since the items are spread over the buckets by their keys, a bucket
+    /// can need to grow before the heap holds this many items, unless the
+    /// space was reserved with
+    /// [`reserve_all_buckets`](RadixHeapMap::reserve_all_buckets).
+    pub fn capacity(&self) -> usize {
+        self.buckets
+            .iter()
+            .chain(self.initial_if_unset())
+            .map(|bucket| bucket.capacity())
+            .sum()
+    }
+
+    /// Reserves capacity for at least `additional` more items.
+    ///
+    /// Items pushed before a top key is set all go to one bucket, which gets
+    /// space for `len() + additional` items if no top key is set yet. Each of
+    /// the other buckets gets an even share of them, so at most about twice
+    /// the capacity is allocated in total. A bucket that more items end up in than its share still has to
+    /// grow; use [`reserve_all_buckets`](RadixHeapMap::reserve_all_buckets)
+    /// to rule that out.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the new capacity overflows `usize`.
+    pub fn reserve(&mut self, additional: usize) {
+        let share = self.bucket_share(additional);
+
+        if self.top.is_none() {
+            let initial = (self.len - self.initial.len()).saturating_add(additional);
+            self.initial.reserve(initial);
+        }
+
+        for bucket in &mut self.buckets {
+            let additional = share.saturating_sub(bucket.len());
+            bucket.reserve(additional);
+        }
+    }
+
+    /// Tries to reserve capacity for at least `additional` more items, like
+    /// [`reserve`](RadixHeapMap::reserve), returning an error instead of
+    /// panicking or aborting if the allocation fails.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let share = self.bucket_share(additional);
+
+        if self.top.is_none() {
+            let initial = (self.len - self.initial.len()).saturating_add(additional);
+            self.initial
+                .try_reserve(initial)
+                .map_err(|_| TryReserveError(()))?;
+        }
+
+        for bucket in &mut self.buckets {
+            let additional = share.saturating_sub(bucket.len());
+            bucket
+                .try_reserve(additional)
+                .map_err(|_| TryReserveError(()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Reserves capacity for at least `additional` more items in every
+    /// bucket, so that they can be pushed and popped without reallocating.
+    ///
+    /// As any item can end up in any bucket, this reserves space for
+    /// `len() + additional` items in each of the `K::RADIX_BITS + 1` buckets,
+    /// and in the bucket used before a top key is set if there is none yet.
+    /// That is about `K::RADIX_BITS` times the memory
+    /// [`reserve`](RadixHeapMap::reserve) uses.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the new capacity overflows `usize`.
+    pub fn reserve_all_buckets(&mut self, additional: usize) {
+        let len = self.len;
+        let unset = self.top.is_none();
+        let initial = Some(&mut self.initial).filter(|_| unset);
+
+        for bucket in self.buckets.iter_mut().chain(initial) {
+            let additional = (len - bucket.len()).saturating_add(additional);
+            bucket.reserve(additional);
+        }
+    }
+
+    /// Tries to reserve capacity for at least `additional` more items in
+    /// every bucket, like
+    /// [`reserve_all_buckets`](RadixHeapMap::reserve_all_buckets), returning
+    /// an error instead of panicking or aborting if the allocation fails.
+    pub fn try_reserve_all_buckets(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let len = self.len;
+        let unset = self.top.is_none();
+        let initial = Some(&mut self.initial).filter(|_| unset);
+
+        for bucket in self.buckets.iter_mut().chain(initial) {
+            let additional = (len - bucket.len()).saturating_add(additional);
+            bucket
+                .try_reserve(additional)
+                .map_err(|_| TryReserveError(()))?;
+        }
+
+        Ok(())
+    }
+
+    /// The bucket used before a top key is set, if there is no top key yet.
+    fn initial_if_unset(&self) -> Option<&Bucket<K, V, A>> {
+        Some(&self.initial).filter(|_| self.top.is_none())
+    }
+
+    /// The capacity `reserve` gives each bucket after the initial one: an
+    /// even share of `len() + additional`, rounded up.
+    fn bucket_share(&self, additional: usize) -> usize {
+        let total = self.len.saturating_add(additional);
+        let buckets = self.buckets.len();
+        total / buckets + usize::from(total % buckets != 0)
+    }
+
     /// Discards as much additional capacity as possible.
     pub fn shrink_to_fit(&mut self) {
         self.initial.shrink_to_fit();
@@ -178,7 +609,7 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
     }
 
     /// Returns an iterator of all key-value pairs in the RadixHeapMap in arbitrary order
-    pub fn iter(&self) -> Iter<K, V> {
+    pub fn iter(&self) -> Iter<'_, K, V, A> {
         Iter {
             cur_bucket: self.initial.iter(),
             buckets: self.buckets.iter(),
@@ -187,28 +618,154 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
     }
 
     /// Returns an iterator of all keys in the RadixHeapMap in arbitrary order
-    pub fn keys(&self) -> Keys<K, V> {
+    pub fn keys(&self) -> Keys<'_, K, V, A> {
         Keys(self.iter())
     }
 
     /// Returns an iterator of all values in the RadixHeapMap in arbitrary order
-    pub fn values(&self) -> Values<K, V> {
+    pub fn values(&self) -> Values<'_, K, V, A> {
         Values(self.iter())
     }
+
+    /// Removes all key-value pairs from the heap and returns them as an
+    /// iterator in arbitrary order.
+    ///
+    /// The heap keeps its allocations and the top key is left unchanged. If
+    /// the iterator is dropped before it is exhausted, the remaining items
+    /// are dropped.
+    pub fn drain(&mut self) -> Drain<'_, K, V, A> {
+        self.occupied.clear();
+        self.greatest.forget();
+
+        Drain {
+            cur_bucket: self.initial.drain(..),
+            buckets: self.buckets.iter_mut(),
+            len: &mut self.len,
+        }
+    }
+
+    /// Retains only the key-value pairs for which `f` returns true.
+    ///
+    /// The top key is left unchanged.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.retain_mut(|k, v| f(k, v));
+    }
+
+    /// Retains only the key-value pairs for which `f` returns true, allowing
+    /// the values to be modified.
+    ///
+    /// The top key is left unchanged.
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.greatest.forget();
+        let len = &mut self.len;
+
+        let mut retain = |bucket: &mut Bucket<K, V, A>| {
+            let kept = partition(bucket, |k, v| !f(k, v));
+            *len -= bucket.len() - kept;
+            bucket.truncate(kept);
+            kept == 0
+        };
+
+        retain(&mut self.initial);
+
+        for (index, bucket) in self.buckets.iter_mut().enumerate() {
+            if retain(bucket) {
+                self.occupied.remove(index);
+            }
+        }
+    }
+
+    /// Returns an iterator that removes the key-value pairs for which `f`
+    /// returns true and yields them in arbitrary order.
+    ///
+    /// The predicate is applied to one bucket at a time as the iterator
+    /// advances. If the iterator is dropped before it is exhausted, like
+    /// `Vec::extract_if`, only the items already yielded are removed, and the
+    /// rest are kept. The top key is left unchanged.
+    pub fn extract_if<F>(&mut self, f: F) -> ExtractIf<'_, K, V, F, A>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.greatest.forget();
+
+        ExtractIf {
+            cur_bucket: None,
+            cur_index: None,
+            kept: 0,
+            initial: Some(&mut self.initial),
+            buckets: self.buckets.iter_mut().enumerate(),
+            occupied: &mut self.occupied,
+            len: &mut self.len,
+            pred: f,
+        }
+    }
+
+    /// Returns an iterator that pops all key-value pairs from the heap in
+    /// priority order.
+    ///
+    /// The heap keeps its allocations. If the iterator is dropped before it
+    /// is exhausted, the remaining items are dropped and the top key is left
+    /// at the last popped key.
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, K, V, O, A> {
+        DrainSorted { heap: self }
+    }
+
+    /// Returns an owning iterator that pops all key-value pairs from the heap
+    /// in priority order.
+    pub fn into_iter_sorted(self) -> IntoIterSorted<K, V, O, A> {
+        IntoIterSorted { heap: self }
+    }
+
+    /// Consumes the heap and returns a vector of all key-value pairs sorted
+    /// in ascending key order, like `BinaryHeap::into_sorted_vec`.
+    ///
+    /// Use `into_iter_sorted` to get the items in the order they would be
+    /// popped instead.
+    pub fn into_sorted_vec(self) -> Vec<(K, V)> {
+        let mut vec: Vec<_> = self.into_iter_sorted().collect();
+
+        if O::DESCENDING {
+            vec.reverse();
+        }
+
+        vec
+    }
+
+    /// Drops all items without changing the top key.
+    fn clear_items(&mut self) {
+        self.len = 0;
+        self.initial.clear();
+        self.occupied.clear();
+        self.greatest.forget();
+
+        for bucket in &mut self.buckets {
+            bucket.clear();
+        }
+    }
 }
 
-impl<K: Radix + Ord + Copy, V> Default for RadixHeapMap<K, V> {
-    fn default() -> RadixHeapMap<K, V> {
-        RadixHeapMap::new()
+impl<K: Radix + Ord + Copy, V, O: Order, A: Allocator + Clone + Default> Default
+    for RadixHeapMap<K, V, O, A>
+{
+    fn default() -> RadixHeapMap<K, V, O, A> {
+        RadixHeapMap::with_top_in(None, A::default())
     }
 }
 
-impl<K: Radix + Ord + Copy, V> FromIterator<(K, V)> for RadixHeapMap<K, V> {
-    fn from_iter<I>(iter: I) -> RadixHeapMap<K, V>
+impl<K: Radix + Ord + Copy, V, O: Order, A: Allocator + Clone + Default> FromIterator<(K, V)>
+    for RadixHeapMap<K, V, O, A>
+{
+    fn from_iter<I>(iter: I) -> RadixHeapMap<K, V, O, A>
     where
         I: IntoIterator<Item = (K, V)>,
     {
-        let mut heap = RadixHeapMap::new();
+        let mut heap = RadixHeapMap::default();
 
         for (k, v) in iter {
             heap.push(k, v);
@@ -218,7 +775,7 @@ impl<K: Radix + Ord + Copy, V> FromIterator<(K, V)> for RadixHeapMap<K, V> {
     }
 }
 
-impl<K: Radix + Ord + Copy, V> Extend<(K, V)> for RadixHeapMap<K, V> {
+impl<K: Radix + Ord + Copy, V, O: Order, A: Allocator> Extend<(K, V)> for RadixHeapMap<K, V, O, A> {
     fn extend<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = (K, V)>,
@@ -229,7 +786,9 @@ impl<K: Radix + Ord + Copy, V> Extend<(K, V)> for RadixHeapMap<K, V> {
     }
 }
 
-impl<'a, K: Radix + Ord + Copy + 'a, V: Copy + 'a> Extend<&'a (K, V)> for RadixHeapMap<K, V> {
+impl<'a, K: Radix + Ord + Copy + 'a, V: Copy + 'a, O: Order, A: Allocator> Extend<&'a (K, V)>
+    for RadixHeapMap<K, V, O, A>
+{
     fn extend<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = &'a (K, V)>,
@@ -240,21 +799,78 @@ impl<'a, K: Radix + Ord + Copy + 'a, V: Copy + 'a> Extend<&'a (K, V)> for RadixH
     }
 }
 
-impl<K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug> fmt::Debug for RadixHeapMap<K, V> {
+impl<K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug, O: Order, A: Allocator> fmt::Debug
+    for RadixHeapMap<K, V, O, A>
+{
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
 }
 
+/// A guard to the greatest element in a RadixHeapMap, created by
+/// [`RadixHeapMap::peek_mut`].
+///
+/// The value can be modified through the guard, but the key cannot, as that
+/// could change where the element belongs in the heap.
+pub struct PeekMut<'a, K, V, O = Max, A: Allocator = Global> {
+    heap: &'a mut RadixHeapMap<K, V, O, A>,
+    bucket: Option<usize>,
+    index: usize,
+}
+
+impl<'a, K: Radix + Ord + Copy, V, O: Order, A: Allocator> PeekMut<'a, K, V, O, A> {
+    /// The key of the peeked element.
+    #[inline]
+    pub fn key(&self) -> K {
+        self.heap.bucket(self.bucket)[self.index].0
+    }
+
+    /// Removes the peeked element from the heap and returns it.
+    ///
+    /// This will set the top key to the extracted key.
+    pub fn pop(this: PeekMut<'a, K, V, O, A>) -> (K, V) {
+        // Popping redistributes the bucket the same way `find_greatest`
+        // expects, so this always returns the peeked element.
+        this.heap.pop().expect("Expected non-empty heap")
+    }
+}
+
+impl<'a, K: Radix + Ord + Copy, V, O: Order, A: Allocator> Deref for PeekMut<'a, K, V, O, A> {
+    type Target = V;
+
+    #[inline]
+    fn deref(&self) -> &V {
+        &self.heap.bucket(self.bucket)[self.index].1
+    }
+}
+
+impl<'a, K: Radix + Ord + Copy, V, O: Order, A: Allocator> DerefMut for PeekMut<'a, K, V, O, A> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut V {
+        &mut self.heap.bucket_mut(self.bucket)[self.index].1
+    }
+}
+
+impl<'a, K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug, O: Order, A: Allocator> fmt::Debug
+    for PeekMut<'a, K, V, O, A>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PeekMut")
+            .field(&self.key())
+            .field(&**self)
+            .finish()
+    }
+}
+
 /// An owning iterator over key-value pairs in a RadixHeapMap.
 #[derive(Clone)]
-pub struct IntoIter<K, V> {
-    cur_bucket: std::vec::IntoIter<(K, V)>,
-    buckets: std::vec::IntoIter<Bucket<K, V>>,
+pub struct IntoIter<K, V, A: Allocator = Global> {
+    cur_bucket: allocator::IntoIter<(K, V), A>,
+    buckets: allocator::IntoIter<Bucket<K, V, A>, A>,
     size: usize,
 }
 
-impl<K, V> Iterator for IntoIter<K, V> {
+impl<K, V, A: Allocator> Iterator for IntoIter<K, V, A> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -281,19 +897,30 @@ impl<K, V> Iterator for IntoIter<K, V> {
     }
 }
 
-impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+impl<K, V, A: Allocator> ExactSizeIterator for IntoIter<K, V, A> {}
 
-impl<K, V> FusedIterator for IntoIter<K, V> {}
+impl<K, V, A: Allocator> FusedIterator for IntoIter<K, V, A> {}
 
 /// An iterator over key-value pairs in a RadixHeapMap.
-#[derive(Clone)]
-pub struct Iter<'a, K, V> {
-    cur_bucket: std::slice::Iter<'a, (K, V)>,
-    buckets: std::slice::Iter<'a, Bucket<K, V>>,
+pub struct Iter<'a, K, V, A: Allocator = Global> {
+    cur_bucket: core::slice::Iter<'a, (K, V)>,
+    buckets: core::slice::Iter<'a, Bucket<K, V, A>>,
     size: usize,
 }
 
-impl<'a, K, V> Iterator for Iter<'a, K, V> {
+// Not derived, as that would require the keys, values and allocator to be
+// `Clone` as well
+impl<'a, K, V, A: Allocator> Clone for Iter<'a, K, V, A> {
+    fn clone(&self) -> Self {
+        Iter {
+            cur_bucket: self.cur_bucket.clone(),
+            buckets: self.buckets.clone(),
+            size: self.size,
+        }
+    }
+}
+
+impl<'a, K, V, A: Allocator> Iterator for Iter<'a, K, V, A> {
     type Item = &'a (K, V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -320,15 +947,20 @@ impl<'a, K, V> Iterator for Iter<'a, K, V> {
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+impl<'a, K, V, A: Allocator> ExactSizeIterator for Iter<'a, K, V, A> {}
 
-impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
+impl<'a, K, V, A: Allocator> FusedIterator for Iter<'a, K, V, A> {}
 
 /// An iterator over keys in a RadixHeapMap.
-#[derive(Clone)]
-pub struct Keys<'a, K, V>(Iter<'a, K, V>);
+pub struct Keys<'a, K, V, A: Allocator = Global>(Iter<'a, K, V, A>);
+
+impl<'a, K, V, A: Allocator> Clone for Keys<'a, K, V, A> {
+    fn clone(&self) -> Self {
+        Keys(self.0.clone())
+    }
+}
 
-impl<'a, K, V> Iterator for Keys<'a, K, V> {
+impl<'a, K, V, A: Allocator> Iterator for Keys<'a, K, V, A> {
     type Item = &'a K;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -347,15 +979,20 @@ impl<'a, K, V> Iterator for Keys<'a, K, V> {
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+impl<'a, K, V, A: Allocator> ExactSizeIterator for Keys<'a, K, V, A> {}
 
-impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+impl<'a, K, V, A: Allocator> FusedIterator for Keys<'a, K, V, A> {}
 
 /// An iterator over values in a RadixHeapMap.
-#[derive(Clone)]
-pub struct Values<'a, K, V>(Iter<'a, K, V>);
+pub struct Values<'a, K, V, A: Allocator = Global>(Iter<'a, K, V, A>);
 
-impl<'a, K, V> Iterator for Values<'a, K, V> {
+impl<'a, K, V, A: Allocator> Clone for Values<'a, K, V, A> {
+    fn clone(&self) -> Self {
+        Values(self.0.clone())
+    }
+}
+
+impl<'a, K, V, A: Allocator> Iterator for Values<'a, K, V, A> {
     type Item = &'a V;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -374,13 +1011,202 @@ impl<'a, K, V> Iterator for Values<'a, K, V> {
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+impl<'a, K, V, A: Allocator> ExactSizeIterator for Values<'a, K, V, A> {}
+
+impl<'a, K, V, A: Allocator> FusedIterator for Values<'a, K, V, A> {}
+
+/// A draining iterator over key-value pairs in a RadixHeapMap, created by
+/// [`RadixHeapMap::drain`].
+pub struct Drain<'a, K, V, A: Allocator = Global> {
+    cur_bucket: allocator::Drain<'a, (K, V), A>,
+    buckets: core::slice::IterMut<'a, Bucket<K, V, A>>,
+    len: &'a mut usize,
+}
+
+impl<'a, K, V, A: Allocator> Iterator for Drain<'a, K, V, A> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let pair @ Some(_) = self.cur_bucket.next() {
+                *self.len -= 1;
+                return pair;
+            } else {
+                self.cur_bucket = self.buckets.next()?.drain(..);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (*self.len, Some(*self.len))
+    }
+}
+
+impl<'a, K, V, A: Allocator> ExactSizeIterator for Drain<'a, K, V, A> {}
+
+impl<'a, K, V, A: Allocator> FusedIterator for Drain<'a, K, V, A> {}
+
+impl<'a, K, V, A: Allocator> Drop for Drain<'a, K, V, A> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+/// An iterator that removes key-value pairs matching a predicate from a
+/// RadixHeapMap, created by [`RadixHeapMap::extract_if`].
+pub struct ExtractIf<'a, K, V, F, A: Allocator = Global> {
+    cur_bucket: Option<&'a mut Bucket<K, V, A>>,
+    /// The index of `cur_bucket`, or `None` for the initial bucket.
+    cur_index: Option<usize>,
+    /// The number of items in `cur_bucket` in front of the matching ones.
+    kept: usize,
+    initial: Option<&'a mut Bucket<K, V, A>>,
+    buckets: core::iter::Enumerate<core::slice::IterMut<'a, Bucket<K, V, A>>>,
+    occupied: &'a mut Occupancy<allocator::Vec<u64, A>>,
+    len: &'a mut usize,
+    pred: F,
+}
+
+impl<'a, K, V, F, A: Allocator> Iterator for ExtractIf<'a, K, V, F, A>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(bucket) = &mut self.cur_bucket {
+                // The matching items stay in the bucket until they are
+                // yielded, so none are lost if the iterator is dropped
+                if bucket.len() > self.kept {
+                    let pair = bucket.pop();
+                    *self.len -= 1;
+
+                    if let (Some(index), true) = (self.cur_index, bucket.is_empty()) {
+                        self.occupied.remove(index);
+                    }
+
+                    return pair;
+                }
+            }
+
+            let (index, bucket) = match self.initial.take() {
+                Some(bucket) => (None, bucket),
+                None => {
+                    let (index, bucket) = self.buckets.next()?;
+                    (Some(index), bucket)
+                }
+            };
+
+            self.kept = partition(bucket, &mut self.pred);
+            self.cur_bucket = Some(bucket);
+            self.cur_index = index;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(*self.len))
+    }
+}
+
+impl<'a, K, V, F, A: Allocator> FusedIterator for ExtractIf<'a, K, V, F, A> where
+    F: FnMut(&K, &mut V) -> bool
+{
+}
+
+/// Moves the items for which `f` returns true to the end of the bucket and
+/// returns the number of items left in front of them.
+///
+/// The items in front keep their relative order, which preserves the order
+/// of ties.
+fn partition<K, V, F>(bucket: &mut [(K, V)], mut f: F) -> usize
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    let mut kept = 0;
+
+    for i in 0..bucket.len() {
+        let (k, v) = &mut bucket[i];
+
+        if !f(k, v) {
+            bucket.swap(kept, i);
+            kept += 1;
+        }
+    }
+
+    kept
+}
+
+/// A draining iterator over key-value pairs in a RadixHeapMap in priority
+/// order, created by [`RadixHeapMap::drain_sorted`].
+pub struct DrainSorted<'a, K: Radix + Ord + Copy, V, O: Order = Max, A: Allocator = Global> {
+    heap: &'a mut RadixHeapMap<K, V, O, A>,
+}
+
+impl<'a, K: Radix + Ord + Copy, V, O: Order, A: Allocator> Iterator
+    for DrainSorted<'a, K, V, O, A>
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<'a, K: Radix + Ord + Copy, V, O: Order, A: Allocator> ExactSizeIterator
+    for DrainSorted<'a, K, V, O, A>
+{
+}
+
+impl<'a, K: Radix + Ord + Copy, V, O: Order, A: Allocator> FusedIterator
+    for DrainSorted<'a, K, V, O, A>
+{
+}
+
+impl<'a, K: Radix + Ord + Copy, V, O: Order, A: Allocator> Drop for DrainSorted<'a, K, V, O, A> {
+    fn drop(&mut self) {
+        self.heap.clear_items();
+    }
+}
+
+/// An owning iterator over key-value pairs in a RadixHeapMap in priority
+/// order, created by [`RadixHeapMap::into_iter_sorted`].
+#[derive(Clone)]
+pub struct IntoIterSorted<K, V, O = Max, A: Allocator = Global> {
+    heap: RadixHeapMap<K, V, O, A>,
+}
+
+impl<K: Radix + Ord + Copy, V, O: Order, A: Allocator> Iterator for IntoIterSorted<K, V, O, A> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
 
-impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
+impl<K: Radix + Ord + Copy, V, O: Order, A: Allocator> ExactSizeIterator
+    for IntoIterSorted<K, V, O, A>
+{
+}
+
+impl<K: Radix + Ord + Copy, V, O: Order, A: Allocator> FusedIterator
+    for IntoIterSorted<K, V, O, A>
+{
+}
 
-impl<K: Radix + Ord + Copy, V> IntoIterator for RadixHeapMap<K, V> {
+impl<K: Radix + Ord + Copy, V, O: Order, A: Allocator> IntoIterator for RadixHeapMap<K, V, O, A> {
     type Item = (K, V);
-    type IntoIter = IntoIter<K, V>;
+    type IntoIter = IntoIter<K, V, A>;
 
     fn into_iter(self) -> Self::IntoIter {
         IntoIter {
@@ -391,9 +1217,11 @@ impl<K: Radix + Ord + Copy, V> IntoIterator for RadixHeapMap<K, V> {
     }
 }
 
-impl<'a, K: Radix + Ord + Copy, V> IntoIterator for &'a RadixHeapMap<K, V> {
+impl<'a, K: Radix + Ord + Copy, V, O: Order, A: Allocator> IntoIterator
+    for &'a RadixHeapMap<K, V, O, A>
+{
     type Item = &'a (K, V);
-    type IntoIter = Iter<'a, K, V>;
+    type IntoIter = Iter<'a, K, V, A>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
@@ -433,7 +1261,25 @@ macro_rules! radix_wrapper_impl {
 
 radix_wrapper_impl!(Reverse);
 radix_wrapper_impl!(Wrapping);
+// Detected by the build script, as it is newer than the minimum supported
+// Rust version
+#[cfg(has_saturating)]
+#[allow(clippy::incompatible_msrv)]
+impl<T: Radix> Radix for Saturating<T> {
+    #[inline]
+    fn radix_similarity(&self, other: &Saturating<T>) -> u32 {
+        self.0.radix_similarity(&other.0)
+    }
 
+    const RADIX_BITS: u32 = T::RADIX_BITS;
+}
+
+// Signed integers are ordered like their bits with the sign bit flipped.
+// Flipping the same bit in both keys does not change their XOR, so the raw
+// bits give the same similarity as that order-preserving encoding. Keys on
+// either side of zero, such as -1 and 0, differ in every bit just like
+// 0x7fff_ffff and 0x8000_0000 do for unsigned keys, which the heap amortizes
+// like any other power of two it crosses.
 macro_rules! radix_int_impl {
     ($t:ty) => {
         impl Radix for $t {
@@ -442,7 +1288,7 @@ macro_rules! radix_int_impl {
                 (self ^ other).leading_zeros()
             }
 
-            const RADIX_BITS: u32 = (std::mem::size_of::<$t>() * 8) as u32;
+            const RADIX_BITS: u32 = (core::mem::size_of::<$t>() * 8) as u32;
         }
     };
 }
@@ -461,27 +1307,51 @@ radix_int_impl!(u64);
 radix_int_impl!(u128);
 radix_int_impl!(usize);
 
-#[cfg(feature = "ordered-float")]
-macro_rules! radix_float_impl {
-    ($t:ty, $bits:ty, $wrapper:path) => {
-        impl Radix for $wrapper {
+macro_rules! radix_nonzero_impl {
+    ($t:ty) => {
+        impl Radix for $t {
             #[inline]
-            fn radix_similarity(&self, other: &$wrapper) -> u32 {
-                let self_bits: $bits = self.to_bits();
-                let other_bits: $bits = other.to_bits();
-                self_bits.radix_similarity(&other_bits)
+            fn radix_similarity(&self, other: &$t) -> u32 {
+                self.get().radix_similarity(&other.get())
             }
 
-            const RADIX_BITS: u32 = <$bits>::RADIX_BITS;
+            const RADIX_BITS: u32 = (core::mem::size_of::<$t>() * 8) as u32;
         }
     };
 }
 
-#[cfg(feature = "ordered-float")]
-radix_float_impl!(f32, u32, ordered_float::NotNan<f32>);
+radix_nonzero_impl!(NonZeroI8);
+radix_nonzero_impl!(NonZeroI16);
+radix_nonzero_impl!(NonZeroI32);
+radix_nonzero_impl!(NonZeroI64);
+radix_nonzero_impl!(NonZeroI128);
+radix_nonzero_impl!(NonZeroIsize);
+
+radix_nonzero_impl!(NonZeroU8);
+radix_nonzero_impl!(NonZeroU16);
+radix_nonzero_impl!(NonZeroU32);
+radix_nonzero_impl!(NonZeroU64);
+radix_nonzero_impl!(NonZeroU128);
+radix_nonzero_impl!(NonZeroUsize);
+
+// Only the low 21 bits of a char are ever set
+impl Radix for char {
+    #[inline]
+    fn radix_similarity(&self, other: &char) -> u32 {
+        (*self as u32 ^ *other as u32).leading_zeros() - (32 - Self::RADIX_BITS)
+    }
+
+    const RADIX_BITS: u32 = 21;
+}
+
+impl Radix for bool {
+    #[inline]
+    fn radix_similarity(&self, other: &bool) -> u32 {
+        (self == other) as u32
+    }
 
-#[cfg(feature = "ordered-float")]
-radix_float_impl!(f64, u64, ordered_float::NotNan<f64>);
+    const RADIX_BITS: u32 = 1;
+}
 
 impl Radix for () {
     #[inline]
@@ -622,14 +1492,61 @@ radix_tuple_impl! {
     }
 }
 
+impl<T: Radix, const N: usize> Radix for [T; N] {
+    #[inline]
+    fn radix_similarity(&self, other: &[T; N]) -> u32 {
+        let mut similarity = 0;
+
+        for (a, b) in self.iter().zip(other) {
+            let s = a.radix_similarity(b);
+            similarity += s;
+            if s < T::RADIX_BITS {
+                break;
+            }
+        }
+
+        similarity
+    }
+
+    const RADIX_BITS: u32 = N as u32 * T::RADIX_BITS;
+}
+
+#[cfg(feature = "std")]
+impl Radix for std::net::Ipv4Addr {
+    #[inline]
+    fn radix_similarity(&self, other: &std::net::Ipv4Addr) -> u32 {
+        self.octets().radix_similarity(&other.octets())
+    }
+
+    const RADIX_BITS: u32 = <[u8; 4]>::RADIX_BITS;
+}
+
+#[cfg(feature = "std")]
+impl Radix for std::net::Ipv6Addr {
+    #[inline]
+    fn radix_similarity(&self, other: &std::net::Ipv6Addr) -> u32 {
+        self.octets().radix_similarity(&other.octets())
+    }
+
+    const RADIX_BITS: u32 = <[u8; 16]>::RADIX_BITS;
+}
+
 #[cfg(test)]
 mod tests {
     extern crate quickcheck;
 
     use self::quickcheck::{quickcheck, TestResult};
+    use super::PeekMut;
     use super::Radix;
     use super::RadixHeapMap;
+    use super::RadixMinHeapMap;
+    #[cfg(has_saturating)]
+    use core::num::Saturating;
+    use core::num::{NonZeroI16, NonZeroU128, NonZeroU32, NonZeroU8};
     use std::cmp::Reverse;
+    #[cfg(feature = "std")]
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use std::{vec, vec::Vec};
 
     #[test]
     fn radix_dist() {
@@ -640,6 +1557,46 @@ mod tests {
         assert!(0u32.radix_distance(&2) == 2);
     }
 
+    #[test]
+    fn array_radix_dist() {
+        fn prop(a: [u8; 3], b: [u8; 3]) -> bool {
+            a.radix_similarity(&b) == (a[0], a[1], a[2]).radix_similarity(&(b[0], b[1], b[2]))
+        }
+
+        quickcheck(prop as fn([u8; 3], [u8; 3]) -> bool);
+
+        assert_eq!(<[u16; 5]>::RADIX_BITS, 80);
+
+        #[cfg(feature = "std")]
+        assert_eq!(
+            Ipv6Addr::LOCALHOST.radix_distance(&Ipv6Addr::UNSPECIFIED),
+            1
+        );
+    }
+
+    #[test]
+    fn char_radix_dist() {
+        assert!('a'.radix_distance(&'a') == 0);
+        assert!('a'.radix_distance(&'b') == 2);
+        assert!('\0'.radix_distance(&char::MAX) == 21);
+    }
+
+    #[test]
+    fn signed_radix_dist() {
+        assert!((-1i32).radix_distance(&0) == 32);
+        assert!((-1i32).radix_distance(&-2) == 1);
+        assert!((-3i32).radix_distance(&-2) == 2);
+        assert!(i32::MIN.radix_distance(&i32::MAX) == 32);
+
+        fn prop(a: i32, b: i32) -> bool {
+            // The order-preserving unsigned encoding of a signed key
+            let flip = |x: i32| (x as u32) ^ (1 << 31);
+            a.radix_distance(&b) == flip(a).radix_distance(&flip(b))
+        }
+
+        quickcheck(prop as fn(i32, i32) -> bool);
+    }
+
     #[test]
     fn clear() {
         let mut heap = RadixHeapMap::new();
@@ -649,6 +1606,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::len_zero, clippy::partialeq_to_none)]
     fn push_pop() {
         let mut heap = RadixHeapMap::new();
         heap.push(0u32, 'a');
@@ -668,6 +1626,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::len_zero, clippy::partialeq_to_none)]
     fn rev_push_pop() {
         let mut heap = RadixHeapMap::new();
         heap.push(Reverse(0), 'a');
@@ -686,6 +1645,38 @@ mod tests {
         assert!(heap.is_empty());
     }
 
+    #[test]
+    fn min_push_pop() {
+        let mut heap = RadixMinHeapMap::new_min();
+        heap.push(0, 'a');
+        heap.push(3, 'b');
+        heap.push(2, 'c');
+
+        assert!(heap.len() == 3);
+        assert!(!heap.is_empty());
+
+        assert!(heap.peek() == Some((0, &'a')));
+        assert!(heap.pop() == Some((0, 'a')));
+        assert!(heap.top() == Some(0));
+        assert!(heap.pop() == Some((2, 'c')));
+        assert!(heap.pop() == Some((3, 'b')));
+        assert!(heap.top() == Some(3));
+        assert!(heap.pop().is_none());
+
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn min_push_pop_panic() {
+        let mut heap = RadixMinHeapMap::new_min_at(2u32);
+        heap.push(5, 'a');
+        heap.push(3, 'b');
+
+        assert!(heap.pop() == Some((3, 'b')));
+        heap.push(2, 'c');
+    }
+
     #[test]
     #[should_panic]
     fn push_pop_panic() {
@@ -698,6 +1689,286 @@ mod tests {
     }
 
     #[test]
+    fn try_push() {
+        let mut heap = RadixHeapMap::new();
+        assert_eq!(heap.try_push(3u32, 'a'), Ok(()));
+        assert_eq!(heap.try_push(8, 'b'), Ok(()));
+        assert_eq!(heap.pop(), Some((8, 'b')));
+
+        let err = heap.try_push(9, 'c').unwrap_err();
+        assert_eq!(err.key(), 9);
+        assert_eq!(err.value(), &'c');
+        assert_eq!(err.top(), 8);
+        assert_eq!(err.into_inner(), (9, 'c'));
+
+        assert_eq!(heap.try_push(8, 'd'), Ok(()));
+        assert_eq!(heap.len(), 2);
+        assert_eq!(heap.pop(), Some((8, 'd')));
+        assert_eq!(heap.pop(), Some((3, 'a')));
+    }
+
+    #[test]
+    fn try_extend() {
+        let mut heap = RadixHeapMap::new_at(5u32);
+        assert_eq!(heap.try_extend(vec![(1, 'a'), (5, 'b')]), Ok(()));
+
+        let err = heap
+            .try_extend(vec![(2, 'c'), (4, 'd'), (6, 'e'), (3, 'f')])
+            .unwrap_err();
+        assert_eq!(err.accepted(), 2);
+        assert_eq!(err.error().key(), 6);
+        assert_eq!(err.into_error().top(), 5);
+
+        let mut vec: Vec<_> = heap.into_iter().collect();
+        vec.sort();
+        assert_eq!(vec, vec![(1, 'a'), (2, 'c'), (4, 'd'), (5, 'b')]);
+    }
+
+    #[test]
+    fn with_capacity() {
+        let heap = RadixHeapMap::<(u128, u128), u64>::with_capacity(1000);
+        assert!(heap.capacity() >= 1000);
+        assert!(heap.initial.capacity() >= 1000);
+
+        // The 257 other buckets share the capacity instead of each getting
+        // all of it
+        assert_eq!(heap.buckets.len(), 257);
+        assert!(heap.buckets.iter().all(|bucket| bucket.capacity() < 8));
+        assert!(heap.capacity() < 2 * 1000 + 257);
+    }
+
+    #[test]
+    fn reserve_all_buckets() {
+        let mut heap = RadixHeapMap::new();
+        heap.reserve_all_buckets(100);
+        assert!(heap.capacity() >= 10 * 100);
+
+        let capacities = |heap: &RadixHeapMap<u8, ()>| {
+            let mut vec: Vec<_> = heap
+                .buckets
+                .iter()
+                .map(|bucket| bucket.capacity())
+                .collect();
+            vec.push(heap.initial.capacity());
+            vec
+        };
+        let before = capacities(&heap);
+
+        heap.extend((0..50).map(|i| (i, ())));
+        assert_eq!(heap.pop(), Some((49, ())));
+        heap.extend((0..50).map(|i| (i, ())));
+
+        while heap.pop().is_some() {}
+
+        assert_eq!(capacities(&heap), before);
+    }
+
+    #[cfg(feature = "allocator-api2")]
+    #[test]
+    fn new_in() {
+        use allocator_api2::alloc::{AllocError, Allocator, Global, Layout};
+        use core::{cell::Cell, ptr::NonNull};
+
+        /// Counts the live allocations made through it.
+        #[derive(Default)]
+        struct Counting(Cell<usize>);
+
+        unsafe impl Allocator for Counting {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.0.set(self.0.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.0.set(self.0.get() - 1);
+                Global.deallocate(ptr, layout)
+            }
+        }
+
+        let alloc = Counting::default();
+
+        {
+            // The list of buckets and the bitset of non-empty ones, and
+            // nothing in the global allocator
+            let mut heap = RadixHeapMap::new_at_in(10u8, &alloc);
+            assert_eq!(alloc.0.get(), 2);
+
+            heap.extend(vec![(3, 'a'), (7, 'b'), (5, 'c')]);
+            assert_eq!(heap.pop(), Some((7, 'b')));
+
+            let mut items: Vec<_> = heap.into_iter().collect();
+            items.sort();
+            assert_eq!(items, vec![(3, 'a'), (5, 'c')]);
+        }
+
+        assert_eq!(alloc.0.get(), 0);
+
+        let mut heap = RadixHeapMap::new_min_in(&alloc);
+        heap.push(5u32, ());
+        heap.push(3, ());
+        assert_eq!(heap.pop(), Some((3, ())));
+        assert_eq!(heap.top(), Some(3));
+    }
+
+    #[test]
+    fn reserve() {
+        let mut heap = RadixHeapMap::new();
+        heap.extend((0..10u32).map(|i| (i, i)));
+        assert_eq!(heap.pop(), Some((9, 9)));
+
+        // Once a top key is set, the space is reserved in the buckets only
+        let buckets = |heap: &RadixHeapMap<u32, u32>| -> usize {
+            heap.buckets.iter().map(|bucket| bucket.capacity()).sum()
+        };
+        let initial = heap.initial.capacity();
+
+        heap.reserve(20);
+        assert!(buckets(&heap) >= 29);
+        assert_eq!(heap.try_reserve(30), Ok(()));
+        assert!(buckets(&heap) >= 39);
+        assert_eq!(heap.capacity(), buckets(&heap));
+        assert_eq!(heap.initial.capacity(), initial);
+        assert!(heap.try_reserve(usize::MAX).is_err());
+
+        heap.shrink_to_fit();
+        assert!(heap.capacity() < 39);
+        assert_eq!(heap.len(), 9);
+
+        assert_eq!(heap.try_reserve_all_buckets(10), Ok(()));
+        assert!(heap.buckets.iter().all(|bucket| bucket.capacity() >= 19));
+        assert_eq!(heap.initial.capacity(), 0);
+        assert!(heap.try_reserve_all_buckets(usize::MAX).is_err());
+
+        let mut heap = RadixHeapMap::<u32, u64>::new_at(1 << 20);
+        heap.reserve(1000);
+        assert_eq!(heap.initial.capacity(), 0);
+        assert!(heap.capacity() >= 1000);
+        assert!(heap.capacity() < 1000 + heap.buckets.len());
+    }
+
+    #[test]
+    fn peek() {
+        let mut heap = RadixHeapMap::new();
+        assert!(heap.peek().is_none());
+
+        heap.push(2u32, 'a');
+        heap.push(5, 'b');
+        heap.push(5, 'c');
+        heap.push(1, 'd');
+
+        assert!(heap.peek() == Some((5, &'c')));
+        assert!(heap.top().is_none());
+        assert!(heap.pop() == Some((5, 'c')));
+
+        heap.push(4, 'e');
+        assert!(heap.peek() == Some((5, &'b')));
+        assert!(heap.pop() == Some((5, 'b')));
+        assert!(heap.peek() == Some((4, &'e')));
+        assert!(heap.top() == Some(5));
+
+        // Peeking must not constrain the top key
+        heap.push(5, 'f');
+        assert!(heap.peek() == Some((5, &'f')));
+        assert!(heap.len() == 4);
+    }
+
+    #[test]
+    fn peek_mut() {
+        let mut heap = RadixHeapMap::new();
+        heap.push(1u32, 10);
+        heap.push(7, 20);
+        heap.push(3, 30);
+
+        {
+            let mut top = heap.peek_mut().unwrap();
+            assert_eq!(top.key(), 7);
+            *top += 1;
+        }
+
+        assert_eq!(heap.top(), None);
+        assert_eq!(heap.peek(), Some((7, &21)));
+
+        let top = heap.peek_mut().unwrap();
+        assert_eq!(PeekMut::pop(top), (7, 21));
+        assert_eq!(heap.top(), Some(7));
+
+        heap.push(6, 40);
+        *heap.peek_mut().unwrap() += 1;
+        assert_eq!(heap.pop(), Some((6, 41)));
+        assert_eq!(heap.pop(), Some((3, 30)));
+        assert_eq!(heap.pop(), Some((1, 10)));
+        assert!(heap.peek_mut().is_none());
+    }
+
+    #[test]
+    fn peek_cached() {
+        // Pushes the keys not after the top key, peeking after every push
+        // and popping at every `None`
+        fn prop(ops: Vec<Option<u32>>) -> bool {
+            let mut heap = RadixHeapMap::new();
+
+            for (i, op) in ops.into_iter().enumerate() {
+                match op {
+                    Some(key) => heap.push(heap.top().map_or(key, |top| key.min(top)), i),
+                    None => {
+                        let peeked = heap.peek().map(|(k, &v)| (k, v));
+                        if peeked != heap.pop() {
+                            return false;
+                        }
+                    }
+                }
+
+                let mut fresh = heap.clone();
+                fresh.greatest.forget();
+
+                if heap.peek() != fresh.peek() || heap.peek() != heap.peek() {
+                    return false;
+                }
+            }
+
+            true
+        }
+
+        quickcheck(prop as fn(Vec<Option<u32>>) -> bool);
+
+        let mut heap = RadixHeapMap::new();
+        heap.extend((0..10u32).map(|i| (i, ())));
+        assert_eq!(heap.peek(), Some((9, &())));
+        assert_eq!(heap.greatest.get(), Some(9));
+
+        heap.push(9, ());
+        assert_eq!(heap.greatest.get(), Some(10));
+        heap.push(3, ());
+        assert_eq!(heap.greatest.get(), Some(10));
+    }
+
+    #[test]
+    fn peek_matches_pop() {
+        fn prop<T: Ord + Radix + Copy>(xs: Vec<T>) -> bool {
+            let mut heap: RadixHeapMap<_, _> =
+                xs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+
+            loop {
+                let peeked = heap.peek().map(|(k, &v)| (k, v));
+
+                if peeked != heap.pop() {
+                    return false;
+                }
+
+                if peeked.is_none() {
+                    return true;
+                }
+            }
+        }
+
+        quickcheck(prop as fn(Vec<u32>) -> bool);
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+        quickcheck(prop as fn(Vec<(u8, i16)>) -> bool);
+        quickcheck(prop as fn(Vec<()>) -> bool);
+    }
+
+    #[test]
+    #[allow(clippy::needless_return)]
     fn sort() {
         fn prop<T: Ord + Radix + Copy>(mut xs: Vec<T>) -> bool {
             let mut heap: RadixHeapMap<_, _> =
@@ -723,10 +1994,126 @@ mod tests {
         quickcheck(prop as fn(Vec<(i64, usize)>) -> bool);
         quickcheck(prop as fn(Vec<i128>) -> bool);
         quickcheck(prop as fn(Vec<u128>) -> bool);
+        quickcheck(prop as fn(Vec<NonZeroU32>) -> bool);
+        quickcheck(prop as fn(Vec<NonZeroU128>) -> bool);
+        quickcheck(prop as fn(Vec<char>) -> bool);
+        quickcheck(prop as fn(Vec<bool>) -> bool);
+        quickcheck(prop as fn(Vec<(bool, char, NonZeroU8)>) -> bool);
+        quickcheck(prop as fn(Vec<[u8; 16]>) -> bool);
+        quickcheck(prop as fn(Vec<[i16; 3]>) -> bool);
+        quickcheck(prop as fn(Vec<[(u8, bool); 2]>) -> bool);
+        quickcheck(prop as fn(Vec<[u32; 0]>) -> bool);
+
+        #[cfg(feature = "std")]
+        {
+            quickcheck(prop as fn(Vec<Ipv4Addr>) -> bool);
+            quickcheck(prop as fn(Vec<Ipv6Addr>) -> bool);
+        }
+
+        // quickcheck only generates unsigned non-zero integers
+        fn non_zero_i16(xs: Vec<i16>) -> bool {
+            prop(xs.into_iter().filter_map(NonZeroI16::new).collect())
+        }
+
+        quickcheck(non_zero_i16 as fn(Vec<i16>) -> bool);
+
+        #[cfg(has_saturating)]
+        {
+            fn saturating(xs: Vec<(i8, u16)>) -> bool {
+                prop(
+                    xs.into_iter()
+                        .map(|(a, b)| (Saturating(a), Saturating(b)))
+                        .collect(),
+                )
+            }
+
+            quickcheck(saturating as fn(Vec<(i8, u16)>) -> bool);
+        }
+    }
+
+    #[test]
+    fn mixed_sign_pushpop() {
+        // Keys start around zero and are pushed while popping, so they keep
+        // crossing it in both directions
+        fn prop(start: i8, steps: Vec<(u8, u8)>) -> bool {
+            let mut heap = RadixHeapMap::new();
+            let mut min_heap = RadixHeapMap::new_min();
+            let mut expected = std::collections::BinaryHeap::new();
+            let mut min_expected = std::collections::BinaryHeap::new();
+
+            heap.push(i32::from(start), ());
+            min_heap.push(i32::from(start), ());
+            expected.push(i32::from(start));
+            min_expected.push(Reverse(i32::from(start)));
+
+            for (down, up) in steps {
+                let top = match heap.pop() {
+                    Some((key, ())) => key,
+                    None => return expected.pop().is_none(),
+                };
+                let min_top = match min_heap.pop() {
+                    Some((key, ())) => key,
+                    None => return min_expected.pop().is_none(),
+                };
+
+                if expected.pop() != Some(top) || min_expected.pop() != Some(Reverse(min_top)) {
+                    return false;
+                }
+
+                for key in [top - i32::from(down), top - i32::from(up)] {
+                    heap.push(key, ());
+                    expected.push(key);
+                }
+
+                for key in [min_top + i32::from(down), min_top + i32::from(up)] {
+                    min_heap.push(key, ());
+                    min_expected.push(Reverse(key));
+                }
+            }
+
+            heap.into_iter_sorted()
+                .map(|(key, ())| key)
+                .eq(std::iter::from_fn(|| expected.pop()))
+                && min_heap
+                    .into_iter_sorted()
+                    .map(|(key, ())| key)
+                    .eq(std::iter::from_fn(|| {
+                        min_expected.pop().map(|Reverse(key)| key)
+                    }))
+        }
+
+        quickcheck(prop as fn(i8, Vec<(u8, u8)>) -> bool);
+    }
+
+    #[test]
+    fn min_sort() {
+        fn prop<T: Ord + Radix + Copy>(mut xs: Vec<T>) -> bool {
+            let mut heap: RadixMinHeapMap<_, _> =
+                xs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+
+            xs.sort_by(|a, b| b.cmp(a));
+
+            while xs.pop() == heap.pop().map(|(k, _)| k) {
+                if xs.is_empty() {
+                    return true;
+                }
+            }
+
+            false
+        }
+
+        quickcheck(prop as fn(Vec<()>) -> bool);
+        quickcheck(prop as fn(Vec<u32>) -> bool);
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+        quickcheck(prop as fn(Vec<(u32, i32)>) -> bool);
+        quickcheck(prop as fn(Vec<u8>) -> bool);
+        quickcheck(prop as fn(Vec<(i64, usize)>) -> bool);
+        quickcheck(prop as fn(Vec<u128>) -> bool);
     }
 
     #[cfg(feature = "ordered-float")]
     #[test]
+    #[allow(clippy::needless_return)]
     fn sort_float() {
         fn prop(xs: Vec<f32>) -> TestResult {
             if xs.iter().any(|x| x.is_nan()) {
@@ -784,6 +2171,204 @@ mod tests {
         quickcheck(prop as fn(Vec<(i64, usize)>) -> TestResult);
     }
 
+    #[test]
+    fn drain() {
+        let mut heap = RadixHeapMap::new();
+        heap.extend(vec![(1u32, 'a'), (5, 'b'), (7, 'c')]);
+        assert_eq!(heap.pop(), Some((7, 'c')));
+        heap.push(6, 'd');
+
+        let mut drain = heap.drain();
+        assert_eq!(drain.len(), 3);
+        let mut vec: Vec<_> = drain.by_ref().collect();
+        assert_eq!(drain.len(), 0);
+        drop(drain);
+
+        vec.sort();
+        assert_eq!(vec, vec![(1, 'a'), (5, 'b'), (6, 'd')]);
+        assert!(heap.is_empty());
+        assert_eq!(heap.top(), Some(7));
+
+        // Dropping the iterator early still empties the heap
+        heap.extend(vec![(3, 'e'), (4, 'f')]);
+        assert_eq!(heap.drain().len(), 2);
+        assert!(heap.is_empty());
+        assert!(heap.pop().is_none());
+        assert_eq!(heap.top(), Some(7));
+    }
+
+    #[test]
+    fn retain() {
+        let mut heap: RadixHeapMap<_, _> = (0..20u32).map(|i| (i, i * 10)).collect();
+        assert_eq!(heap.pop(), Some((19, 190)));
+
+        heap.retain(|&k, _| k % 3 != 0);
+        assert_eq!(heap.len(), 12);
+        assert_eq!(heap.top(), Some(19));
+
+        heap.retain_mut(|&k, v| {
+            *v += 1;
+            k > 4
+        });
+        assert_eq!(heap.len(), 9);
+
+        let vec: Vec<_> = heap.drain_sorted().collect();
+        assert_eq!(
+            vec,
+            vec![
+                (17, 171),
+                (16, 161),
+                (14, 141),
+                (13, 131),
+                (11, 111),
+                (10, 101),
+                (8, 81),
+                (7, 71),
+                (5, 51)
+            ]
+        );
+    }
+
+    #[test]
+    fn retain_keeps_tie_order() {
+        let mut heap = RadixHeapMap::new();
+        heap.extend(vec![(1u32, 'a'), (1, 'b'), (1, 'c'), (1, 'd')]);
+        heap.retain(|_, &v| v != 'b');
+
+        let vec: Vec<_> = heap.drain_sorted().map(|(_, v)| v).collect();
+        assert_eq!(vec, vec!['d', 'c', 'a']);
+    }
+
+    #[test]
+    fn extract_if() {
+        fn prop(xs: Vec<(u16, bool)>) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().copied().collect();
+            let popped = heap.pop();
+            let top = heap.top();
+
+            let mut extracted: Vec<_> = heap.extract_if(|_, &mut v| v).collect();
+            let mut kept: Vec<_> = heap.iter().copied().collect();
+
+            let mut expected = xs;
+            if let Some(i) = expected.iter().position(|&x| Some(x) == popped) {
+                expected.remove(i);
+            }
+            expected.sort();
+            let mut expected_kept = expected.clone();
+            expected.retain(|&(_, v)| v);
+            expected_kept.retain(|&(_, v)| !v);
+
+            extracted.sort();
+            kept.sort();
+
+            heap.len() == kept.len()
+                && heap.top() == top
+                && extracted == expected
+                && kept == expected_kept
+        }
+
+        quickcheck(prop as fn(Vec<(u16, bool)>) -> bool);
+    }
+
+    #[test]
+    fn retain_sort() {
+        fn prop(xs: Vec<(u128, bool)>, pops: usize) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().copied().collect();
+            let mut expected = xs;
+            expected.sort_by_key(|&(k, _)| k);
+
+            for _ in 0..pops % (expected.len() + 1) {
+                if heap.pop().map(|(k, _)| k) != expected.pop().map(|(k, _)| k) {
+                    return false;
+                }
+            }
+
+            heap.retain(|_, &v| v);
+            expected.retain(|&(_, v)| v);
+
+            heap.len() == expected.len()
+                && std::iter::from_fn(|| heap.pop().map(|(k, _)| k))
+                    .eq(expected.into_iter().rev().map(|(k, _)| k))
+        }
+
+        quickcheck(prop as fn(Vec<(u128, bool)>, usize) -> bool);
+    }
+
+    #[test]
+    fn extract_if_drop() {
+        let mut heap: RadixHeapMap<_, _> = (0..10u32).map(|i| (i, ())).collect();
+        assert_eq!(heap.pop(), Some((9, ())));
+
+        let (extracted, ()) = heap.extract_if(|&k, _| k % 2 == 0).next().unwrap();
+
+        // Only the extracted item is removed
+        assert_eq!(heap.len(), 8);
+        assert_eq!(heap.len(), heap.iter().count());
+        assert_eq!(heap.keys().filter(|&&k| k % 2 == 1).count(), 4);
+        assert!(!heap.keys().any(|&k| k == extracted));
+        assert_eq!(heap.top(), Some(9));
+
+        let mut heap = RadixHeapMap::new_at(100u32);
+        heap.extend(vec![(64, 'a'), (66, 'b'), (68, 'c'), (70, 'd'), (65, 'e')]);
+        assert!(heap.extract_if(|&k, _| k % 2 == 0).next().is_some());
+        assert_eq!(heap.len(), 4);
+
+        assert_eq!(heap.keys().filter(|&&k| k % 2 == 0).count(), 3);
+    }
+
+    #[test]
+    fn drain_sorted() {
+        let mut heap: RadixHeapMap<_, _> = vec![(3u32, 'a'), (9, 'b'), (1, 'c'), (9, 'd')]
+            .into_iter()
+            .collect();
+
+        {
+            let mut drain = heap.drain_sorted();
+            assert_eq!(drain.len(), 4);
+            assert_eq!(drain.next(), Some((9, 'd')));
+            assert_eq!(drain.next(), Some((9, 'b')));
+            assert_eq!(drain.len(), 2);
+        }
+
+        assert!(heap.is_empty());
+        assert_eq!(heap.top(), Some(9));
+
+        heap.push(7, 'e');
+        heap.push(2, 'f');
+        let vec: Vec<_> = heap.drain_sorted().collect();
+        assert_eq!(vec, vec![(7, 'e'), (2, 'f')]);
+        assert_eq!(heap.top(), Some(2));
+    }
+
+    #[test]
+    fn into_iter_sorted() {
+        fn prop<T: Ord + Radix + Copy>(mut xs: Vec<T>) -> bool {
+            let heap: RadixHeapMap<_, _> = xs.iter().map(|&d| (d, ())).collect();
+            let min_heap: RadixMinHeapMap<_, _> = xs.iter().map(|&d| (d, ())).collect();
+
+            xs.sort();
+
+            let iter = heap.clone().into_iter_sorted();
+            let sorted_vec = heap.into_sorted_vec();
+
+            iter.len() == xs.len()
+                && iter.map(|(k, ())| k).eq(xs.iter().rev().copied())
+                && sorted_vec
+                    .into_iter()
+                    .map(|(k, ())| k)
+                    .eq(xs.iter().copied())
+                && min_heap
+                    .into_sorted_vec()
+                    .into_iter()
+                    .map(|(k, ())| k)
+                    .eq(xs)
+        }
+
+        quickcheck(prop as fn(Vec<u32>) -> bool);
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+        quickcheck(prop as fn(Vec<(u8, i16)>) -> bool);
+    }
+
     #[test]
     fn into_iter_inital() {
         let mut heap = RadixHeapMap::new();
@@ -0,0 +1,109 @@
+use alloc::{vec, vec::Vec};
+
+use crate::allocator::{self, Allocator};
+
+/// A bitset of which buckets of a radix heap are non-empty.
+///
+/// This lets the first non-empty bucket be found with `trailing_zeros`
+/// instead of scanning every bucket, which matters for keys with many radix
+/// bits, such as `u128` or tuples.
+///
+/// The words are a `Vec` by default, one in the allocator of the heap, or an
+/// array for heaps that cannot allocate.
+#[derive(Clone, Debug)]
+pub(crate) struct Occupancy<W = Vec<u64>> {
+    words: W,
+}
+
+impl Occupancy {
+    /// Creates an empty bitset for the given number of buckets.
+    pub(crate) fn new(buckets: usize) -> Occupancy {
+        Occupancy {
+            words: vec![0; buckets / 64 + 1],
+        }
+    }
+}
+
+impl<A: Allocator> Occupancy<allocator::Vec<u64, A>> {
+    /// Creates an empty bitset for the given number of buckets, storing its
+    /// words in `alloc`.
+    pub(crate) fn new_in(buckets: usize, alloc: A) -> Occupancy<allocator::Vec<u64, A>> {
+        let mut words = allocator::Vec::new_in(alloc);
+        words.resize(buckets / 64 + 1, 0);
+        Occupancy { words }
+    }
+}
+
+impl<const WORDS: usize> Occupancy<[u64; WORDS]> {
+    /// Creates an empty bitset for up to `64 * WORDS` buckets.
+    pub(crate) fn fixed() -> Occupancy<[u64; WORDS]> {
+        Occupancy { words: [0; WORDS] }
+    }
+}
+
+impl<W: AsRef<[u64]> + AsMut<[u64]>> Occupancy<W> {
+    #[inline]
+    pub(crate) fn insert(&mut self, bucket: usize) {
+        self.words.as_mut()[bucket / 64] |= 1 << (bucket % 64);
+    }
+
+    #[inline]
+    pub(crate) fn remove(&mut self, bucket: usize) {
+        self.words.as_mut()[bucket / 64] &= !(1 << (bucket % 64));
+    }
+
+    /// Returns the index of the first non-empty bucket.
+    #[inline]
+    pub(crate) fn first(&self) -> Option<usize> {
+        self.words
+            .as_ref()
+            .iter()
+            .enumerate()
+            .find(|(_, &word)| word != 0)
+            .map(|(i, word)| i * 64 + word.trailing_zeros() as usize)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        for word in self.words.as_mut() {
+            *word = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Occupancy;
+
+    #[test]
+    fn first() {
+        let mut occupancy = Occupancy::new(129);
+        assert_eq!(occupancy.first(), None);
+
+        occupancy.insert(128);
+        occupancy.insert(64);
+        assert_eq!(occupancy.first(), Some(64));
+
+        occupancy.insert(3);
+        assert_eq!(occupancy.first(), Some(3));
+
+        occupancy.remove(3);
+        occupancy.remove(64);
+        assert_eq!(occupancy.first(), Some(128));
+
+        occupancy.clear();
+        assert_eq!(occupancy.first(), None);
+    }
+
+    #[test]
+    fn fixed() {
+        let mut occupancy = Occupancy::<[u64; 3]>::fixed();
+        assert_eq!(occupancy.first(), None);
+
+        occupancy.insert(191);
+        occupancy.insert(65);
+        assert_eq!(occupancy.first(), Some(65));
+
+        occupancy.clear();
+        assert_eq!(occupancy.first(), None);
+    }
+}
@@ -0,0 +1,48 @@
+use core::cmp::Ordering;
+
+/// The direction in which a radix heap pops its keys.
+///
+/// This is implemented by [`Max`] and [`Min`] and cannot be implemented
+/// outside of this crate.
+pub trait Order: private::Sealed {
+    /// Compares two keys such that the key that is popped first compares as
+    /// greater.
+    fn compare<K: Ord>(a: &K, b: &K) -> Ordering;
+
+    /// Whether keys are popped in descending order.
+    #[doc(hidden)]
+    const DESCENDING: bool;
+}
+
+/// Pops the greatest key first. This is the default order of a radix heap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Max;
+
+impl Order for Max {
+    #[inline]
+    fn compare<K: Ord>(a: &K, b: &K) -> Ordering {
+        a.cmp(b)
+    }
+
+    const DESCENDING: bool = true;
+}
+
+/// Pops the least key first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Min;
+
+impl Order for Min {
+    #[inline]
+    fn compare<K: Ord>(a: &K, b: &K) -> Ordering {
+        b.cmp(a)
+    }
+
+    const DESCENDING: bool = false;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::Max {}
+    impl Sealed for super::Min {}
+}
@@ -0,0 +1,333 @@
+//! Keys with several small integer fields packed into one integer.
+
+use crate::Radix;
+
+/// A key packing several small unsigned integer fields into a `u64` or a
+/// `u128`.
+///
+/// A key is built with [`PackedKey::builder`] by adding fields from the most
+/// significant to the least significant, each with a width in bits. Keys are
+/// then ordered lexicographically by their fields, like a tuple of them,
+/// but comparing them and computing their radix similarity is as fast as for
+/// a single integer. Fields added with [`reverse`](PackedKeyBuilder::reverse)
+/// are ordered in reverse, like [`Reverse`](core::cmp::Reverse).
+///
+/// The fields are read back with [`PackedKey::reader`], giving the same
+/// widths in the same order.
+///
+/// # Example
+///
+/// ```
+/// use radix_heap::{PackedKey, RadixHeapMap};
+///
+/// // Pop the least distance first, breaking ties by the greatest priority
+/// let key = |distance: u64, priority: u64| {
+///     PackedKey::builder()
+///         .reverse(distance, 32)
+///         .field(priority, 32)
+///         .build()
+/// };
+///
+/// let mut heap = RadixHeapMap::new();
+/// heap.push(key(10, 3), 'a');
+/// heap.push(key(8, 2), 'b');
+/// heap.push(key(10, 5), 'c');
+///
+/// let (key, value) = heap.pop().unwrap();
+/// let mut reader = key.reader();
+/// assert_eq!(reader.reverse(32), 8);
+/// assert_eq!(reader.field(32), 2);
+/// assert_eq!(value, 'b');
+///
+/// assert_eq!(heap.pop().map(|(_, v)| v), Some('c'));
+/// assert_eq!(heap.pop().map(|(_, v)| v), Some('a'));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackedKey<T = u64>(T);
+
+/// A builder for a [`PackedKey`], adding its fields from the most
+/// significant to the least significant.
+#[derive(Clone, Copy, Debug)]
+pub struct PackedKeyBuilder<T = u64> {
+    bits: T,
+    used: u32,
+}
+
+/// Reads the fields of a [`PackedKey`] in the order they were added.
+#[derive(Clone, Copy, Debug)]
+pub struct PackedKeyReader<T = u64> {
+    bits: T,
+    used: u32,
+}
+
+impl<T: PackedBits> PackedKey<T> {
+    /// Returns a builder for a key with no fields.
+    #[inline]
+    pub fn builder() -> PackedKeyBuilder<T> {
+        PackedKeyBuilder {
+            bits: T::ZERO,
+            used: 0,
+        }
+    }
+
+    /// Returns a reader for the fields of the key.
+    #[inline]
+    pub fn reader(self) -> PackedKeyReader<T> {
+        PackedKeyReader {
+            bits: self.0,
+            used: 0,
+        }
+    }
+
+    /// Creates a key from its packed bits.
+    #[inline]
+    pub fn from_bits(bits: T) -> PackedKey<T> {
+        PackedKey(bits)
+    }
+
+    /// Returns the packed bits of the key, which are ordered like the key.
+    #[inline]
+    pub fn to_bits(self) -> T {
+        self.0
+    }
+}
+
+impl<T: PackedBits> PackedKeyBuilder<T> {
+    /// Adds a field of `width` bits, ordered like `value`.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `value` does not fit in `width` bits, or if there are fewer
+    /// than `width` bits left in the key.
+    #[inline]
+    pub fn field(self, value: T, width: u32) -> PackedKeyBuilder<T> {
+        assert!(
+            width <= T::BITS - self.used,
+            "Field does not fit in the key"
+        );
+        assert!(value.fits(width), "Value does not fit in the field");
+
+        let used = self.used + width;
+        PackedKeyBuilder {
+            bits: self.bits.insert(value, T::BITS - used),
+            used,
+        }
+    }
+
+    /// Adds a field of `width` bits, ordered in reverse of `value`.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `value` does not fit in `width` bits, or if there are fewer
+    /// than `width` bits left in the key.
+    #[inline]
+    pub fn reverse(self, value: T, width: u32) -> PackedKeyBuilder<T> {
+        assert!(value.fits(width), "Value does not fit in the field");
+        self.field(value.invert(width), width)
+    }
+
+    /// Builds the key. Any bits left are zero.
+    #[inline]
+    pub fn build(self) -> PackedKey<T> {
+        PackedKey(self.bits)
+    }
+}
+
+impl<T: PackedBits> PackedKeyReader<T> {
+    /// Reads a field of `width` bits added with
+    /// [`field`](PackedKeyBuilder::field).
+    ///
+    /// Panics
+    /// ------
+    /// Panics if there are fewer than `width` bits left in the key.
+    #[inline]
+    pub fn field(&mut self, width: u32) -> T {
+        assert!(
+            width <= T::BITS - self.used,
+            "Field does not fit in the key"
+        );
+
+        self.used += width;
+        self.bits.extract(T::BITS - self.used, width)
+    }
+
+    /// Reads a field of `width` bits added with
+    /// [`reverse`](PackedKeyBuilder::reverse).
+    ///
+    /// Panics
+    /// ------
+    /// Panics if there are fewer than `width` bits left in the key.
+    #[inline]
+    pub fn reverse(&mut self, width: u32) -> T {
+        self.field(width).invert(width)
+    }
+}
+
+impl<T: Radix> Radix for PackedKey<T> {
+    #[inline]
+    fn radix_similarity(&self, other: &PackedKey<T>) -> u32 {
+        self.0.radix_similarity(&other.0)
+    }
+
+    const RADIX_BITS: u32 = T::RADIX_BITS;
+}
+
+/// The integers keys can be packed into, which are `u64` and `u128`.
+///
+/// This cannot be implemented outside of this crate.
+pub trait PackedBits: Copy + private::Sealed {
+    #[doc(hidden)]
+    const BITS: u32;
+
+    #[doc(hidden)]
+    const ZERO: Self;
+
+    /// Whether the value fits in the low `width` bits.
+    #[doc(hidden)]
+    fn fits(self, width: u32) -> bool;
+
+    /// Flips the low `width` bits, which reverses the order of the values
+    /// fitting in them.
+    #[doc(hidden)]
+    fn invert(self, width: u32) -> Self;
+
+    /// Sets the bits starting at bit `shift` to `value`, which are zero.
+    #[doc(hidden)]
+    fn insert(self, value: Self, shift: u32) -> Self;
+
+    /// Returns the `width` bits starting at bit `shift`.
+    #[doc(hidden)]
+    fn extract(self, shift: u32, width: u32) -> Self;
+}
+
+macro_rules! packed_bits_impl {
+    ($t:ty) => {
+        impl PackedBits for $t {
+            const BITS: u32 = <$t>::BITS;
+            const ZERO: $t = 0;
+
+            #[inline]
+            fn fits(self, width: u32) -> bool {
+                width >= <$t>::BITS || self >> width == 0
+            }
+
+            #[inline]
+            fn invert(self, width: u32) -> $t {
+                if width >= <$t>::BITS {
+                    !self
+                } else {
+                    self ^ ((1 << width) - 1)
+                }
+            }
+
+            #[inline]
+            fn insert(self, value: $t, shift: u32) -> $t {
+                // An empty field may be inserted past the last bit
+                self | value.checked_shl(shift).unwrap_or(0)
+            }
+
+            #[inline]
+            fn extract(self, shift: u32, width: u32) -> $t {
+                self.checked_shr(shift).unwrap_or(0) & 0.invert(width)
+            }
+        }
+
+        impl private::Sealed for $t {}
+    };
+}
+
+packed_bits_impl!(u64);
+packed_bits_impl!(u128);
+
+mod private {
+    pub trait Sealed {}
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::PackedKey;
+    use crate::{Radix, RadixHeapMap};
+    use std::{cmp::Reverse, vec::Vec};
+
+    fn pack((a, b, c): (u8, u16, u32)) -> PackedKey {
+        PackedKey::builder()
+            .field(u64::from(a), 8)
+            .reverse(u64::from(b), 16)
+            .field(u64::from(c), 32)
+            .build()
+    }
+
+    #[test]
+    fn read() {
+        fn prop(fields: (u8, u16, u32)) -> bool {
+            let mut reader = pack(fields).reader();
+
+            reader.field(8) == u64::from(fields.0)
+                && reader.reverse(16) == u64::from(fields.1)
+                && reader.field(32) == u64::from(fields.2)
+        }
+
+        quickcheck(prop as fn((u8, u16, u32)) -> bool);
+    }
+
+    #[test]
+    fn full_width() {
+        let key = PackedKey::<u128>::builder()
+            .reverse(0, 0)
+            .field(u128::MAX, 128)
+            .build();
+        assert_eq!(key.to_bits(), u128::MAX);
+
+        let mut reader = key.reader();
+        assert_eq!(reader.reverse(0), 0);
+        assert_eq!(reader.field(128), u128::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn value_too_wide() {
+        PackedKey::builder().field(256u64, 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fields_too_wide() {
+        PackedKey::builder().field(1u64, 60).field(1, 5);
+    }
+
+    #[test]
+    fn order() {
+        fn prop(a: (u8, u16, u32), b: (u8, u16, u32)) -> bool {
+            let tuple = |(a, b, c): (u8, u16, u32)| (a, Reverse(b), c);
+            pack(a).cmp(&pack(b)) == tuple(a).cmp(&tuple(b))
+        }
+
+        quickcheck(prop as fn((u8, u16, u32), (u8, u16, u32)) -> bool);
+    }
+
+    #[test]
+    fn radix_dist() {
+        assert_eq!(pack((1, 2, 3)).radix_distance(&pack((1, 2, 3))), 0);
+        // The fields take the high 56 bits
+        assert_eq!(pack((1, 2, 3)).radix_distance(&pack((1, 2, 2))), 9);
+        assert_eq!(pack((1, 2, 3)).radix_distance(&pack((0, 2, 3))), 57);
+    }
+
+    #[test]
+    fn sort() {
+        fn prop(mut xs: Vec<(u8, u16, u32)>) -> bool {
+            let heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (pack(x), x)).collect();
+
+            xs.sort_by_key(|&(a, b, c)| (a, Reverse(b), c));
+
+            xs.into_iter()
+                .rev()
+                .eq(heap.into_iter_sorted().map(|(_, x)| x))
+        }
+
+        quickcheck(prop as fn(Vec<(u8, u16, u32)>) -> bool);
+    }
+}
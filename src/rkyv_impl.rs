@@ -0,0 +1,195 @@
+//! `rkyv` support, enabled by the `rkyv` feature.
+//!
+//! A heap is archived as its top key and a flat vector of its items, with
+//! the items of each bucket kept together. The archive can be inspected
+//! without deserializing it, and deserializing only pushes every item back
+//! into its bucket.
+
+use core::fmt;
+
+use rkyv::{
+    bytecheck::CheckBytes,
+    munge::munge,
+    option::ArchivedOption,
+    rancor::{Fallible, Source},
+    ser::{Allocator as SerAllocator, Writer},
+    tuple::ArchivedTuple2,
+    vec::{ArchivedVec, VecResolver},
+    Archive, Deserialize, Place, Portable, Serialize,
+};
+
+use crate::{Allocator, Order, Radix, RadixHeapMap};
+
+/// An archived [`RadixHeapMap`], which can be inspected without
+/// deserializing it.
+///
+/// `K` and `V` are the archived key and value types. Deserializing it with
+/// `rkyv` rebuilds the heap with the same top key and items.
+#[derive(Portable, CheckBytes)]
+#[rkyv(crate = rkyv)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[repr(C)]
+pub struct ArchivedRadixHeapMap<K, V> {
+    top: ArchivedOption<K>,
+
+    /// The items of the initial bucket followed by those of every other
+    /// bucket, each in the order they were pushed.
+    items: ArchivedVec<ArchivedTuple2<K, V>>,
+}
+
+impl<K, V> ArchivedRadixHeapMap<K, V> {
+    /// Returns the number of elements in the heap
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if there is no elements in the heap
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The top key of the heap when it was archived.
+    #[inline]
+    pub fn top(&self) -> Option<&K> {
+        self.top.as_ref()
+    }
+
+    /// Returns an iterator of all key-value pairs in the heap in arbitrary
+    /// order
+    pub fn iter(&self) -> core::slice::Iter<'_, ArchivedTuple2<K, V>> {
+        self.items.iter()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ArchivedRadixHeapMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, O, A> Archive for RadixHeapMap<K, V, O, A>
+where
+    K: Radix + Ord + Copy + Archive,
+    V: Archive,
+    O: Order,
+    A: Allocator,
+{
+    type Archived = ArchivedRadixHeapMap<K::Archived, V::Archived>;
+    type Resolver = (Option<K::Resolver>, VecResolver);
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedRadixHeapMap { top, items } = out);
+        self.top.resolve(resolver.0, top);
+        ArchivedVec::resolve_from_len(self.len, resolver.1, items);
+    }
+}
+
+impl<K, V, O, A, S> Serialize<S> for RadixHeapMap<K, V, O, A>
+where
+    K: Radix + Ord + Copy + Serialize<S>,
+    V: Serialize<S>,
+    O: Order,
+    A: Allocator,
+    S: Fallible + SerAllocator + Writer + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let top = self.top.serialize(serializer)?;
+        let items = ArchivedVec::serialize_from_iter::<(K, V), _, _>(self.iter(), serializer)?;
+        Ok((top, items))
+    }
+}
+
+impl<K, V, O, A, D> Deserialize<RadixHeapMap<K, V, O, A>, D>
+    for ArchivedRadixHeapMap<K::Archived, V::Archived>
+where
+    K: Radix + Ord + Copy + Archive,
+    V: Archive,
+    K::Archived: Deserialize<K, D>,
+    V::Archived: Deserialize<V, D>,
+    O: Order,
+    A: Allocator + Clone + Default,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<RadixHeapMap<K, V, O, A>, D::Error> {
+        let top = match self.top() {
+            Some(top) => Some(top.deserialize(deserializer)?),
+            None => None,
+        };
+
+        let mut heap = RadixHeapMap::with_top_in(top, A::default());
+
+        for ArchivedTuple2(key, value) in self.iter() {
+            let key = key.deserialize(deserializer)?;
+            let value = value.deserialize(deserializer)?;
+
+            heap.try_push(key, value)
+                .map_err(|_| D::Error::new(OrderedBeforeTop))?;
+        }
+
+        Ok(heap)
+    }
+}
+
+/// The error returned when deserializing an archived heap with an item
+/// ordered before the top key.
+#[derive(Debug)]
+struct OrderedBeforeTop;
+
+impl fmt::Display for OrderedBeforeTop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("key is ordered before the current top key")
+    }
+}
+
+impl core::error::Error for OrderedBeforeTop {}
+
+#[cfg(test)]
+mod tests {
+    use super::ArchivedRadixHeapMap;
+    use crate::{RadixHeapMap, RadixMinHeapMap};
+    use rkyv::{rancor::Error, rend::u32_le, Archived};
+    use std::{vec, vec::Vec};
+
+    type ArchivedHeap = ArchivedRadixHeapMap<Archived<u32>, Archived<char>>;
+
+    #[test]
+    fn access() {
+        let mut heap = RadixHeapMap::new();
+        heap.extend([(4u32, 'a'), (7, 'b'), (2, 'c'), (4, 'd')]);
+        assert_eq!(heap.pop(), Some((7, 'b')));
+
+        let bytes = rkyv::to_bytes::<Error>(&heap).unwrap();
+        let archived = rkyv::access::<ArchivedHeap, Error>(&bytes).unwrap();
+
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived.top(), Some(&u32_le::from_native(7)));
+
+        let mut items: Vec<_> = archived
+            .iter()
+            .map(|item| (item.0.to_native(), item.1.to_native()))
+            .collect();
+        items.sort();
+        assert_eq!(items, vec![(2, 'c'), (4, 'a'), (4, 'd')]);
+
+        let mut heap: RadixHeapMap<u32, char> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(heap.top(), Some(7));
+        assert_eq!(heap.pop(), Some((4, 'd')));
+        assert_eq!(heap.pop(), Some((4, 'a')));
+        assert_eq!(heap.pop(), Some((2, 'c')));
+        assert!(heap.pop().is_none());
+    }
+
+    #[test]
+    fn rejects_key_past_top() {
+        // Laid out like an archived min-heap with the top key 5
+        let bytes = rkyv::to_bytes::<Error>(&(Some(5u8), vec![(9u8, ()), (3, ())])).unwrap();
+        let archived =
+            rkyv::access::<ArchivedRadixHeapMap<Archived<u8>, ()>, Error>(&bytes).unwrap();
+
+        assert_eq!(archived.len(), 2);
+        assert!(rkyv::deserialize::<RadixMinHeapMap<u8, ()>, Error>(archived).is_err());
+    }
+}
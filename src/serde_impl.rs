@@ -0,0 +1,266 @@
+//! `Serialize` and `Deserialize` implementations, enabled by the `serde`
+//! feature.
+//!
+//! A heap is serialized as a struct with its top key and a sequence of all
+//! its items in arbitrary order.
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor},
+    ser::{Serialize, SerializeStruct, Serializer},
+};
+
+use crate::{Allocator, Order, Radix, RadixHeapMap};
+
+const FIELDS: &[&str] = &["top", "items"];
+
+impl<K, V, O, A> Serialize for RadixHeapMap<K, V, O, A>
+where
+    K: Radix + Ord + Copy + Serialize,
+    V: Serialize,
+    O: Order,
+    A: Allocator,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("RadixHeapMap", 2)?;
+        state.serialize_field("top", &self.top)?;
+        state.serialize_field("items", &Items(self))?;
+        state.end()
+    }
+}
+
+struct Items<'a, K, V, O, A: Allocator>(&'a RadixHeapMap<K, V, O, A>);
+
+impl<'a, K, V, O, A> Serialize for Items<'a, K, V, O, A>
+where
+    K: Radix + Ord + Copy + Serialize,
+    V: Serialize,
+    O: Order,
+    A: Allocator,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+impl<'de, K, V, O, A> Deserialize<'de> for RadixHeapMap<K, V, O, A>
+where
+    K: Radix + Ord + Copy + Deserialize<'de>,
+    V: Deserialize<'de>,
+    O: Order,
+    A: Allocator + Clone + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("RadixHeapMap", FIELDS, HeapVisitor(PhantomData))
+    }
+}
+
+enum Field {
+    Top,
+    Items,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Field, D::Error> {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("`top` or `items`")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+                match value {
+                    "top" => Ok(Field::Top),
+                    "items" => Ok(Field::Items),
+                    _ => Err(E::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct HeapVisitor<K, V, O, A: Allocator>(PhantomData<RadixHeapMap<K, V, O, A>>);
+
+impl<K, V, O, A> HeapVisitor<K, V, O, A>
+where
+    K: Radix + Ord + Copy,
+    O: Order,
+    A: Allocator + Clone + Default,
+{
+    /// Builds the heap by pushing every item, which puts them in the right
+    /// buckets for the top key.
+    fn build<E: de::Error>(
+        top: Option<K>,
+        items: Vec<(K, V)>,
+    ) -> Result<RadixHeapMap<K, V, O, A>, E> {
+        let mut heap = RadixHeapMap::with_top_in(top, A::default());
+        heap.try_extend(items).map_err(E::custom)?;
+        Ok(heap)
+    }
+}
+
+impl<'de, K, V, O, A> Visitor<'de> for HeapVisitor<K, V, O, A>
+where
+    K: Radix + Ord + Copy + Deserialize<'de>,
+    V: Deserialize<'de>,
+    O: Order,
+    A: Allocator + Clone + Default,
+{
+    type Value = RadixHeapMap<K, V, O, A>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("struct RadixHeapMap")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let top = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let items = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Self::build(top, items)
+    }
+
+    fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+        let mut top = None;
+        let mut items = None;
+
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Top if top.is_some() => return Err(de::Error::duplicate_field("top")),
+                Field::Top => top = Some(map.next_value()?),
+                Field::Items if items.is_some() => return Err(de::Error::duplicate_field("items")),
+                Field::Items => items = Some(map.next_value()?),
+            }
+        }
+
+        let top = top.ok_or_else(|| de::Error::missing_field("top"))?;
+        let items = items.ok_or_else(|| de::Error::missing_field("items"))?;
+
+        Self::build(top, items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_test;
+
+    use self::serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, Token};
+    use crate::{RadixHeapMap, RadixMinHeapMap};
+    use serde::{Deserialize, Deserializer};
+    use std::{vec, vec::Vec};
+
+    /// The top key of a deserialized heap and the keys popped from it.
+    #[derive(Debug, PartialEq)]
+    struct Popped(Option<u8>, Vec<u8>);
+
+    impl<'de> Deserialize<'de> for Popped {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Popped, D::Error> {
+            let mut heap = RadixMinHeapMap::<u8, ()>::deserialize(deserializer)?;
+            let top = heap.top();
+            let keys = std::iter::from_fn(|| heap.pop().map(|(k, ())| k)).collect();
+            Ok(Popped(top, keys))
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        let mut heap = RadixHeapMap::new();
+        heap.extend([(4u32, 'a'), (7, 'b'), (2, 'c')]);
+        assert_eq!(heap.pop(), Some((7, 'b')));
+
+        assert_ser_tokens(
+            &heap,
+            &[
+                Token::Struct {
+                    name: "RadixHeapMap",
+                    len: 2,
+                },
+                Token::Str("top"),
+                Token::Some,
+                Token::U32(7),
+                Token::Str("items"),
+                Token::Seq { len: Some(2) },
+                Token::Tuple { len: 2 },
+                Token::U32(4),
+                Token::Char('a'),
+                Token::TupleEnd,
+                Token::Tuple { len: 2 },
+                Token::U32(2),
+                Token::Char('c'),
+                Token::TupleEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn deserialize_rebuckets() {
+        assert_de_tokens(
+            &Popped(Some(5), vec![5, 9, 200]),
+            &[
+                Token::Struct {
+                    name: "RadixHeapMap",
+                    len: 2,
+                },
+                Token::Str("top"),
+                Token::Some,
+                Token::U8(5),
+                Token::Str("items"),
+                Token::Seq { len: Some(3) },
+                Token::Tuple { len: 2 },
+                Token::U8(200),
+                Token::Unit,
+                Token::TupleEnd,
+                Token::Tuple { len: 2 },
+                Token::U8(9),
+                Token::Unit,
+                Token::TupleEnd,
+                Token::Tuple { len: 2 },
+                Token::U8(5),
+                Token::Unit,
+                Token::TupleEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn rejects_key_past_top() {
+        assert_de_tokens_error::<RadixHeapMap<u32, ()>>(
+            &[
+                Token::Struct {
+                    name: "RadixHeapMap",
+                    len: 2,
+                },
+                Token::Str("top"),
+                Token::Some,
+                Token::U32(7),
+                Token::Str("items"),
+                Token::Seq { len: Some(2) },
+                Token::Tuple { len: 2 },
+                Token::U32(4),
+                Token::Unit,
+                Token::TupleEnd,
+                Token::Tuple { len: 2 },
+                Token::U32(8),
+                Token::Unit,
+                Token::TupleEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+            "key is ordered before the current top key after 1 accepted items",
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Keys for durations and points in time.
+
+use core::time::Duration;
+
+use crate::Radix;
+
+// `as_nanos` is less than 2^94 for any duration
+impl Radix for Duration {
+    #[inline]
+    fn radix_similarity(&self, other: &Duration) -> u32 {
+        let similarity = self.as_nanos().radix_similarity(&other.as_nanos());
+        similarity - (u128::RADIX_BITS - Self::RADIX_BITS)
+    }
+
+    const RADIX_BITS: u32 = 94;
+}
+
+#[cfg(feature = "std")]
+pub use self::timestamp::Timestamp;
+
+#[cfg(feature = "std")]
+mod timestamp {
+    use core::{
+        convert::TryFrom,
+        ops::{Add, Sub},
+        time::Duration,
+    };
+    use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+    use crate::{Radix, TimestampRangeError};
+
+    /// A point in time as a key, stored as signed nanoseconds since an epoch.
+    ///
+    /// A timestamp is ordered like the time it was created from, and can be
+    /// used as the key of a min-heap to pop the earliest deadline first.
+    ///
+    /// A timestamp created from a [`SystemTime`] counts from the Unix epoch,
+    /// and one created from an [`Instant`] counts from an instant given as
+    /// the epoch. Timestamps with different epochs must not be compared.
+    /// Either covers about 292 years before and after its epoch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use radix_heap::{RadixHeapMap, Timestamp};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let epoch = Instant::now();
+    /// let now = Timestamp::from_instant(epoch, epoch);
+    ///
+    /// let mut deadlines = RadixHeapMap::new_min();
+    /// deadlines.push(now + Duration::from_millis(30), "flush");
+    /// deadlines.push(now + Duration::from_millis(10), "retry");
+    ///
+    /// let (deadline, task) = deadlines.pop().unwrap();
+    /// assert_eq!(task, "retry");
+    /// assert_eq!(deadline.to_instant(epoch), epoch + Duration::from_millis(10));
+    /// ```
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Timestamp(i64);
+
+    impl Timestamp {
+        /// Creates a timestamp from nanoseconds since its epoch.
+        #[inline]
+        pub const fn from_nanos(nanos: i64) -> Timestamp {
+            Timestamp(nanos)
+        }
+
+        /// Returns the nanoseconds since the epoch of the timestamp, which
+        /// are negative before it.
+        #[inline]
+        pub const fn as_nanos(self) -> i64 {
+            self.0
+        }
+
+        /// Creates a timestamp from an instant, counting from `epoch`.
+        ///
+        /// Panics
+        /// ------
+        /// Panics if the instant is more than about 292 years from `epoch`.
+        pub fn from_instant(instant: Instant, epoch: Instant) -> Timestamp {
+            if instant >= epoch {
+                Timestamp::after(instant - epoch)
+            } else {
+                Timestamp::before(epoch - instant)
+            }
+        }
+
+        /// Returns the instant of a timestamp created with
+        /// [`from_instant`](Timestamp::from_instant) with the same `epoch`.
+        ///
+        /// Panics
+        /// ------
+        /// Panics if the instant cannot be represented on this platform.
+        pub fn to_instant(self, epoch: Instant) -> Instant {
+            if self.0 >= 0 {
+                epoch + self.since_epoch()
+            } else {
+                epoch - self.since_epoch()
+            }
+        }
+
+        /// Creates a timestamp from a system time, counting from the Unix
+        /// epoch.
+        ///
+        /// Use [`checked_from_system_time`](Timestamp::checked_from_system_time)
+        /// or `Timestamp::try_from` for times that may be out of range.
+        ///
+        /// Panics
+        /// ------
+        /// Panics if the time is more than about 292 years from the Unix
+        /// epoch.
+        pub fn from_system_time(time: SystemTime) -> Timestamp {
+            Timestamp::checked_from_system_time(time).expect("timestamp out of range")
+        }
+
+        /// Creates a timestamp from a system time, counting from the Unix
+        /// epoch, or returns `None` if the time is more than about 292 years
+        /// from it.
+        pub fn checked_from_system_time(time: SystemTime) -> Option<Timestamp> {
+            match time.duration_since(UNIX_EPOCH) {
+                Ok(after) => Timestamp::checked_after(after),
+                Err(err) => Timestamp::checked_before(err.duration()),
+            }
+        }
+
+        /// Returns the system time of a timestamp created with
+        /// [`from_system_time`](Timestamp::from_system_time).
+        ///
+        /// Panics
+        /// ------
+        /// Panics if the time cannot be represented on this platform.
+        pub fn to_system_time(self) -> SystemTime {
+            if self.0 >= 0 {
+                UNIX_EPOCH + self.since_epoch()
+            } else {
+                UNIX_EPOCH - self.since_epoch()
+            }
+        }
+
+        fn after(duration: Duration) -> Timestamp {
+            Timestamp::checked_after(duration).expect("timestamp out of range")
+        }
+
+        fn before(duration: Duration) -> Timestamp {
+            Timestamp::checked_before(duration).expect("timestamp out of range")
+        }
+
+        fn checked_after(duration: Duration) -> Option<Timestamp> {
+            i64::try_from(duration.as_nanos()).ok().map(Timestamp)
+        }
+
+        fn checked_before(duration: Duration) -> Option<Timestamp> {
+            // Negated first, as `i64::MIN` has no positive counterpart
+            let nanos = -(duration.as_nanos() as i128);
+            i64::try_from(nanos).ok().map(Timestamp)
+        }
+
+        /// The distance from the epoch in either direction.
+        fn since_epoch(self) -> Duration {
+            Duration::from_nanos(self.0.unsigned_abs())
+        }
+    }
+
+    impl TryFrom<SystemTime> for Timestamp {
+        type Error = TimestampRangeError;
+
+        #[inline]
+        fn try_from(time: SystemTime) -> Result<Timestamp, TimestampRangeError> {
+            Timestamp::checked_from_system_time(time).ok_or(TimestampRangeError(()))
+        }
+    }
+
+    impl Add<Duration> for Timestamp {
+        type Output = Timestamp;
+
+        /// Panics
+        /// ------
+        /// Panics on overflow.
+        #[inline]
+        fn add(self, duration: Duration) -> Timestamp {
+            self.0
+                .checked_add(Timestamp::after(duration).0)
+                .map(Timestamp)
+                .expect("overflow when adding duration to timestamp")
+        }
+    }
+
+    impl Sub<Duration> for Timestamp {
+        type Output = Timestamp;
+
+        /// Panics
+        /// ------
+        /// Panics on overflow.
+        #[inline]
+        fn sub(self, duration: Duration) -> Timestamp {
+            self.0
+                .checked_sub(Timestamp::after(duration).0)
+                .map(Timestamp)
+                .expect("overflow when subtracting duration from timestamp")
+        }
+    }
+
+    impl Radix for Timestamp {
+        #[inline]
+        fn radix_similarity(&self, other: &Timestamp) -> u32 {
+            self.0.radix_similarity(&other.0)
+        }
+
+        const RADIX_BITS: u32 = i64::RADIX_BITS;
+    }
+
+    #[cfg(test)]
+    mod tests {
+        extern crate quickcheck;
+
+        use self::quickcheck::quickcheck;
+        use super::Timestamp;
+        use crate::TimestampRangeError;
+        use core::convert::TryFrom;
+        use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+        #[test]
+        fn instant() {
+            let epoch = Instant::now() + Duration::from_secs(60);
+
+            for &offset in &[0, 1, 999_999_999, 1_000_000_000, 3_600_000_000_007] {
+                let after = epoch + Duration::from_nanos(offset);
+                let before = epoch - Duration::from_nanos(offset);
+
+                let ts = Timestamp::from_instant(after, epoch);
+                assert_eq!(ts.as_nanos(), offset as i64);
+                assert_eq!(ts.to_instant(epoch), after);
+
+                let ts = Timestamp::from_instant(before, epoch);
+                assert_eq!(ts.as_nanos(), -(offset as i64));
+                assert_eq!(ts.to_instant(epoch), before);
+            }
+        }
+
+        #[test]
+        fn system_time() {
+            fn prop(nanos: i64) -> bool {
+                let ts = Timestamp::from_nanos(nanos);
+                Timestamp::from_system_time(ts.to_system_time()) == ts
+            }
+
+            quickcheck(prop as fn(i64) -> bool);
+
+            let before = UNIX_EPOCH - Duration::from_nanos(5);
+            assert_eq!(Timestamp::try_from(before).map(Timestamp::as_nanos), Ok(-5));
+            assert!(
+                Timestamp::from_system_time(before)
+                    < Timestamp::from_system_time(SystemTime::now())
+            );
+        }
+
+        #[test]
+        fn system_time_out_of_range() {
+            let far = UNIX_EPOCH + Duration::from_secs(400 * 365 * 86400);
+            assert_eq!(Timestamp::checked_from_system_time(far), None);
+            assert_eq!(Timestamp::try_from(far), Err(TimestampRangeError(())));
+        }
+
+        #[test]
+        #[should_panic]
+        fn from_system_time_out_of_range() {
+            Timestamp::from_system_time(UNIX_EPOCH + Duration::from_secs(400 * 365 * 86400));
+        }
+
+        #[test]
+        fn arithmetic() {
+            let ts = Timestamp::from_nanos(-3);
+            assert_eq!(ts + Duration::from_nanos(5), Timestamp::from_nanos(2));
+            assert_eq!(
+                ts - Duration::from_secs(1),
+                Timestamp::from_nanos(-1_000_000_003)
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn add_overflow() {
+            let _ = Timestamp::from_nanos(i64::MAX) + Duration::from_nanos(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use crate::{Radix, RadixHeapMap};
+    use core::time::Duration;
+    use std::vec::Vec;
+
+    #[test]
+    fn duration_radix_dist() {
+        let max = Duration::new(u64::MAX, 999_999_999);
+        assert_eq!(max.radix_distance(&Duration::ZERO), 94);
+
+        // Durations are compared by their nanoseconds, not their seconds
+        assert_eq!(
+            Duration::from_nanos(999_999_999).radix_distance(&Duration::from_secs(1)),
+            10
+        );
+    }
+
+    #[test]
+    fn sort() {
+        fn prop<T: Ord + Radix + Copy>(mut xs: Vec<T>) -> bool {
+            let heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, ())).collect();
+
+            xs.sort();
+            xs.into_iter()
+                .rev()
+                .eq(heap.into_iter_sorted().map(|(k, ())| k))
+        }
+
+        fn durations(xs: Vec<(u64, u32)>) -> bool {
+            prop(
+                xs.into_iter()
+                    .map(|(secs, nanos)| Duration::new(secs, nanos % 1_000_000_000))
+                    .collect(),
+            )
+        }
+
+        quickcheck(durations as fn(Vec<(u64, u32)>) -> bool);
+
+        #[cfg(feature = "std")]
+        {
+            fn timestamps(xs: Vec<i64>) -> bool {
+                prop(xs.into_iter().map(super::Timestamp::from_nanos).collect())
+            }
+
+            quickcheck(timestamps as fn(Vec<i64>) -> bool);
+        }
+    }
+}